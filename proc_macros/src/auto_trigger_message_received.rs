@@ -259,11 +259,13 @@ pub fn generate(attr: TokenStream, item: TokenStream) -> TokenStream {
                             commands.trigger(#trigger_struct_ident {
                                 message: data.clone(),
                                 sender: self.sender.clone(),
+                                tick_sent: self.tick_sent,
                             });
                         } else {
                             commands.trigger_targets(#trigger_struct_ident {
                                 message: data.clone(),
                                 sender: self.sender.clone(),
+                                tick_sent: self.tick_sent,
                             }, targets);
                         }
                     }
@@ -314,6 +316,7 @@ pub fn generate(attr: TokenStream, item: TokenStream) -> TokenStream {
                     commands.trigger_targets(#trigger_struct_ident {
                         message: data.clone(),
                         sender: self.sender.clone(),
+                                tick_sent: self.tick_sent,
                     }, target);
                 }
             }
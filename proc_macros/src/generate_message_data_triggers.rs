@@ -26,6 +26,8 @@ pub fn generate(item: TokenStream) -> TokenStream {
                         #[deref]
                         pub message: #field_type,
                         pub sender: Option<Entity>,
+                        /// The tick the sender set when sending the message
+                        pub tick_sent: u64,
                     }
                 };
                 generated_structs.push(generated);
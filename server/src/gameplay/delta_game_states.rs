@@ -0,0 +1,91 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+use shared::networking::messages::{
+    message_container::{
+        AcknowledgeGameStateTrigger, NetworkMessageType, RequestFullGameStateTrigger,
+    },
+    message_data::game_state::{GameState, GameStateDelta},
+};
+
+/// How many game states can be sent to a client without being acknowledged
+/// If a client falls further behind, we assume the states got lost and send a full snapshot again
+pub const MAX_UNACKNOWLEDGED_STATES: usize = 30;
+
+/// The game states sent to a client using delta states, to compute the next delta from
+#[derive(Debug, Component, Default)]
+pub struct SentGameStates {
+    /// The newest state the client acknowledged, all deltas are based on it
+    acknowledged: Option<GameState>,
+    /// The states sent after the acknowledged one, oldest first
+    unacknowledged: VecDeque<GameState>,
+}
+
+impl SentGameStates {
+    /// Remembers the state and returns the message to send it with:
+    /// a delta if the client acknowledged an earlier state, the full state otherwise
+    pub fn next_message(&mut self, state: GameState) -> NetworkMessageType {
+        if self.unacknowledged.len() >= MAX_UNACKNOWLEDGED_STATES {
+            warn!(
+                "Client did not acknowledge the last {} game states, sending a full snapshot",
+                MAX_UNACKNOWLEDGED_STATES
+            );
+            self.reset();
+        }
+
+        let message = match &self.acknowledged {
+            Some(acknowledged) => {
+                NetworkMessageType::GameStateDelta(GameStateDelta::between(acknowledged, &state))
+            }
+            None => NetworkMessageType::GameState(state.clone()),
+        };
+        self.unacknowledged.push_back(state);
+
+        message
+    }
+
+    /// Makes the state of the tick the base of all following deltas
+    pub fn acknowledge(&mut self, tick: u64) {
+        match self
+            .unacknowledged
+            .iter()
+            .position(|state| state.tick == tick)
+        {
+            Some(index) => {
+                // Older states can't be acknowledged anymore
+                self.acknowledged = self.unacknowledged.drain(..=index).next_back();
+            }
+            None => warn!("Client acknowledged unknown game state of tick {}", tick),
+        }
+    }
+
+    /// Forgets all states, so the next one is sent in full
+    pub fn reset(&mut self) {
+        self.acknowledged = None;
+        self.unacknowledged.clear();
+    }
+}
+
+pub fn handle_acknowledge_game_state(
+    trigger: Trigger<AcknowledgeGameStateTrigger>,
+    mut sent_game_states: Query<&mut SentGameStates>,
+) {
+    let client_entity = trigger.entity();
+    let mut sent_game_states = sent_game_states
+        .get_mut(client_entity)
+        .expect("Failed to get sent game states");
+
+    sent_game_states.acknowledge(trigger.tick);
+}
+
+pub fn handle_request_full_game_state(
+    trigger: Trigger<RequestFullGameStateTrigger>,
+    mut sent_game_states: Query<&mut SentGameStates>,
+) {
+    let client_entity = trigger.entity();
+    let mut sent_game_states = sent_game_states
+        .get_mut(client_entity)
+        .expect("Failed to get sent game states");
+
+    sent_game_states.reset();
+}
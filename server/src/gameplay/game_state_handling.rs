@@ -1,8 +1,12 @@
 use bevy::{prelude::*, utils::hashbrown::HashSet};
 use shared::{
     game::{
-        game_state::{ClientState, PersonalizedClientGameState, ProjectileState},
-        player_handling::{ShootCooldown, TankBodyMarker, TankTurretMarker},
+        game_state::{ClientState, MineState, PersonalizedClientGameState, ProjectileState},
+        mine_handling::MineMarker,
+        player_handling::{
+            Health, PlayerState, ShootCooldown, TankBodyMarker, TankModules, TankMomentum,
+            TankTurretMarker,
+        },
         projectile_handling::ProjectileMarker,
         tank_types::TankType,
     },
@@ -10,23 +14,40 @@ use shared::{
         lobby_management::{lobby_management::LobbyManagementSystemParam, LobbyState},
         messages::{
             message_container::{MessageContainer, MessageTarget, NetworkMessageType},
+            message_data::{
+                game_state::GameState,
+                protocol_negotiation::{NegotiatedProtocol, ProtocolCapability},
+            },
             message_queue::OutMessageQueue,
         },
     },
 };
 
-use crate::gameplay::triggers::UpdateClientGameStatesTrigger;
+use crate::gameplay::{delta_game_states::SentGameStates, triggers::UpdateClientGameStatesTrigger};
 
 use super::triggers::{
     AddStateUpdateToQueue, SendOutgoingMessagesTrigger, UpdateLobbyGameStateTrigger,
 };
 
+/// Everything of a tank that ends up in the game state
+type TankStates<'a> = (
+    &'a Transform,
+    &'a TankType,
+    &'a TankBodyMarker,
+    &'a ShootCooldown,
+    &'a Health,
+    &'a PlayerState,
+    &'a TankMomentum,
+    &'a TankModules,
+);
+
 pub fn update_lobby_state(
     trigger: Trigger<UpdateLobbyGameStateTrigger>,
     mut lobby_management: LobbyManagementSystemParam,
-    tanks: Query<(&Transform, &TankType, &TankBodyMarker, &ShootCooldown)>,
+    tanks: Query<TankStates>,
     turrets: Query<&Transform, With<TankTurretMarker>>,
     projectiles: Query<(&Transform, &ProjectileMarker), With<ProjectileMarker>>,
+    mines: Query<(&Transform, &MineMarker)>,
     mut commands: Commands,
 ) {
     let lobby_entity = trigger.entity();
@@ -45,14 +66,29 @@ pub fn update_lobby_state(
         .iter()
         .map(|entity| *entity)
         .collect::<HashSet<_>>();
+    let mine_entities = lobby_management
+        .get_lobby(lobby_entity)
+        .expect("Failed to get lobby")
+        .mines
+        .iter()
+        .copied()
+        .collect::<HashSet<_>>();
     let mut lobby_game_state = lobby_management
         .get_lobby_gamestate_mut(lobby_entity)
         .expect("Failed to get lobby game state");
 
     // Updating client states of all players
     for player_entity in player_entities.iter() {
-        let (tank_transform, _tank_type, tank_body, shoot_cooldown) =
-            tanks.get(*player_entity).expect("Failed to get tank");
+        let (
+            tank_transform,
+            _tank_type,
+            tank_body,
+            shoot_cooldown,
+            health,
+            player_state,
+            momentum,
+            modules,
+        ) = tanks.get(*player_entity).expect("Failed to get tank");
 
         let relative_turret_transform = turrets
             .get(tank_body.turret.expect("Failed to get turret entity"))
@@ -65,6 +101,10 @@ pub fn update_lobby_state(
         client_state.transform_body = Some(tank_transform.clone());
         client_state.transform_turret = Some(relative_turret_transform.clone());
         client_state.shoot_cooldown = shoot_cooldown.ticks_left;
+        client_state.speed = Some(momentum.speed);
+        client_state.health = Some(health.health);
+        client_state.state = Some(player_state.clone());
+        client_state.modules = Some(modules.clone());
     }
 
     // Updating states of all projectiles and removing those that are not in the world anymore from the game state
@@ -89,6 +129,21 @@ pub fn update_lobby_state(
             });
     }
 
+    // Updating states of all mines and removing those that detonated from the game state
+    lobby_game_state
+        .mines
+        .retain(|entity, _| mine_entities.contains(entity));
+    for mine_entity in mine_entities.iter() {
+        let (mine_transform, mine_data) = mines
+            .get(*mine_entity)
+            .expect("Failed to get mine transform");
+
+        lobby_game_state
+            .mines
+            .entry(*mine_entity)
+            .or_insert_with(|| MineState::new(*mine_entity, mine_data.owner, *mine_transform));
+    }
+
     commands.trigger_targets(UpdateClientGameStatesTrigger, player_entities);
 }
 
@@ -129,7 +184,8 @@ pub fn add_current_game_state_to_message_queue(
     trigger: Trigger<AddStateUpdateToQueue>,
     lobby_management: LobbyManagementSystemParam,
     mut out_message_queues: Query<&mut OutMessageQueue>,
-    client_states: Query<&PersonalizedClientGameState>,
+    mut client_states: Query<&mut PersonalizedClientGameState>,
+    mut state_senders: Query<(&mut SentGameStates, Option<&NegotiatedProtocol>)>,
     mut commands: Commands,
 ) {
     let lobby_entity = trigger.entity();
@@ -145,14 +201,19 @@ pub fn add_current_game_state_to_message_queue(
         let mut out_message_queue = out_message_queues
             .get_mut(*player_entity)
             .expect("Failed to get client out message queue");
-        let client_state = client_states
-            .get(*player_entity)
+        let mut client_state = client_states
+            .get_mut(*player_entity)
             .expect("Failed to get client state");
 
         let message = MessageContainer::new(
             MessageTarget::Client(*player_entity),
-            NetworkMessageType::GameState(client_state.clone().into()),
+            game_state_message(
+                client_state.clone().into(),
+                state_senders.get_mut(*player_entity).ok(),
+            ),
         );
+        // Each command is only acknowledged once
+        client_state.command_acknowledgements.clear();
 
         // Make sure the game state is sent before any other messages
         out_message_queue.push_front(message);
@@ -166,7 +227,10 @@ pub fn add_current_game_state_to_message_queue(
 
         let message = MessageContainer::new(
             MessageTarget::Client(*spectator_entity),
-            NetworkMessageType::GameState(lobby_state.clone().into()),
+            game_state_message(
+                lobby_state.clone().into(),
+                state_senders.get_mut(*spectator_entity).ok(),
+            ),
         );
 
         // Make sure the game state is sent before any other messages
@@ -175,3 +239,18 @@ pub fn add_current_game_state_to_message_queue(
 
     commands.trigger_targets(SendOutgoingMessagesTrigger, lobby_entity);
 }
+
+/// Sends only the changes since the last acknowledged state to clients that negotiated delta states
+pub fn game_state_message(
+    state: GameState,
+    state_sender: Option<(Mut<SentGameStates>, Option<&NegotiatedProtocol>)>,
+) -> NetworkMessageType {
+    match state_sender {
+        Some((mut sent_game_states, Some(protocol)))
+            if protocol.has_capability(&ProtocolCapability::DeltaStates) =>
+        {
+            sent_game_states.next_message(state)
+        }
+        _ => NetworkMessageType::GameState(state),
+    }
+}
@@ -2,7 +2,7 @@ use bevy::prelude::*;
 use shared::{
     game::collision_handling::{
         components::{Collider, CollisionLayer, WantedTransform},
-        triggers::{CollidedWithTrigger, CollidedWithWorldTrigger},
+        triggers::{CollidedWithTrigger, CollidedWithWorldTrigger, FellFromLedgeTrigger},
     },
     networking::lobby_management::{InLobby, MyLobby},
 };
@@ -19,7 +19,8 @@ use crate::gameplay::triggers::{CalculateCollisionsTrigger, FinishedNextSimulati
 ///
 /// The function first gathers the floor heights of all tiles under the footprint, ensuring that each tile exists
 /// within the map’s bounds. It then verifies that the slope between the highest tile and its neighbors does not
-/// exceed the collider’s maximum allowed slope.
+/// exceed the collider’s maximum allowed slope. Steeper ledges may only be driven down, and only if they are not
+/// higher than the collider’s max_drop. Every minion that falls down such a ledge is reported, so it may suffer.
 ///
 /// A special case is observed when the collider’s max_slope is 0.0—this denotes a flying entity, not meant to climb.
 /// In such cases, the vertical component of the candidate translation is not adjusted to match the floor height.
@@ -60,6 +61,8 @@ pub fn check_world_collision_and_apply_movement(
 
     // A thread-safe hoard for entities that encounter collision misfortune.
     let collided_entities = Mutex::new(Vec::new());
+    // And another one for those who plunged down a ledge, together with the height of their fall.
+    let fallen_entities = Mutex::new(Vec::new());
 
     // --- World Collision Check ---
     colliders.par_iter_mut().for_each(
@@ -80,6 +83,8 @@ pub fn check_world_collision_and_apply_movement(
 
             let mut safe_translation = current.translation;
             let mut safe_rotation = current.rotation;
+            let mut safe_floor = current.translation.y - collider.half_size.y;
+            let mut fallen_height = 0.0;
             let mut collision_happened = false;
 
             // Advance in meticulous steps, checking for collisions with the world.
@@ -161,9 +166,21 @@ pub fn check_world_collision_and_apply_movement(
                         .iter()
                         .any(|&h| (candidate_floor - h).abs() > collider.max_slope)
                     {
-                        collision_happened = true;
-                        break;
+                        // Too steep to climb, but we may still drive down the ledge,
+                        // as long as we don't end up higher than we currently are.
+                        let lowest_floor = tile_heights.iter().cloned().fold(f32::MAX, f32::min);
+                        let is_driving_down = candidate_floor <= safe_floor + collider.max_slope
+                            && candidate_floor - lowest_floor <= collider.max_drop;
+
+                        if !is_driving_down {
+                            collision_happened = true;
+                            break;
+                        }
+                    }
+                    if safe_floor - candidate_floor > collider.max_slope {
+                        fallen_height += safe_floor - candidate_floor;
                     }
+                    safe_floor = candidate_floor;
                     safe_translation = Vec3::new(
                         candidate_translation.x,
                         candidate_floor + collider.half_size.y,
@@ -176,6 +193,12 @@ pub fn check_world_collision_and_apply_movement(
             if collision_happened {
                 collided_entities.lock().unwrap().push(entity);
             }
+            if fallen_height > 0.0 {
+                fallen_entities
+                    .lock()
+                    .unwrap()
+                    .push((entity, fallen_height));
+            }
 
             transform.translation = safe_translation;
             transform.rotation = safe_rotation;
@@ -192,6 +215,9 @@ pub fn check_world_collision_and_apply_movement(
         CollidedWithWorldTrigger,
         collided_entities.into_inner().unwrap(),
     );
+    for (entity, height) in fallen_entities.into_inner().unwrap() {
+        commands.trigger_targets(FellFromLedgeTrigger { height }, entity);
+    }
     commands.trigger_targets(CalculateCollisionsTrigger, my_lobby_entity);
 }
 
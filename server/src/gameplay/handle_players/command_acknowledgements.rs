@@ -0,0 +1,64 @@
+use bevy::prelude::*;
+use shared::{
+    game::game_state::{LobbyGameState, PersonalizedClientGameState},
+    networking::{
+        lobby_management::InLobby,
+        messages::message_data::command_acknowledgement::{
+            AcknowledgedCommand, CommandAcknowledgement, CommandRejectionReason,
+        },
+    },
+};
+
+/// Triggered on a client whenever one of its commands was applied or rejected
+#[derive(Debug, Event)]
+pub struct CommandProcessedTrigger {
+    pub command: AcknowledgedCommand,
+    pub tick_sent: u64,
+    pub rejection_reason: Option<CommandRejectionReason>,
+}
+
+impl CommandProcessedTrigger {
+    pub fn applied(command: AcknowledgedCommand, tick_sent: u64) -> Self {
+        Self {
+            command,
+            tick_sent,
+            rejection_reason: None,
+        }
+    }
+
+    pub fn rejected(
+        command: AcknowledgedCommand,
+        tick_sent: u64,
+        rejection_reason: CommandRejectionReason,
+    ) -> Self {
+        Self {
+            command,
+            tick_sent,
+            rejection_reason: Some(rejection_reason),
+        }
+    }
+}
+
+/// Adds the acknowledgement to the client's next game state
+pub fn record_command_acknowledgement(
+    trigger: Trigger<CommandProcessedTrigger>,
+    mut clients: Query<(&mut PersonalizedClientGameState, &InLobby)>,
+    lobby_states: Query<&LobbyGameState>,
+) {
+    let client_entity = trigger.entity();
+    let (mut client_state, in_lobby) = clients
+        .get_mut(client_entity)
+        .expect("Failed to get client state");
+    let lobby_state = lobby_states
+        .get(in_lobby.0)
+        .expect("Failed to get lobby game state");
+
+    client_state
+        .command_acknowledgements
+        .push(CommandAcknowledgement {
+            command: trigger.command.clone(),
+            tick_sent: trigger.tick_sent,
+            tick_processed: lobby_state.tick,
+            rejection_reason: trigger.rejection_reason.clone(),
+        });
+}
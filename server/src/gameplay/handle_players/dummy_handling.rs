@@ -55,6 +55,7 @@ pub fn simulate_movement(
             commands.trigger_targets(
                 MoveTankCommandTrigger {
                     sender: None,
+                    tick_sent: 0,
                     message: MoveTankCommand {
                         distance: tank_config.move_speed,
                     },
@@ -66,6 +67,7 @@ pub fn simulate_movement(
             commands.trigger_targets(
                 RotateTankBodyCommandTrigger {
                     sender: None,
+                    tick_sent: 0,
                     message: RotateTankBodyCommand {
                         angle: -tank_config.body_rotation_speed,
                     },
@@ -77,6 +79,7 @@ pub fn simulate_movement(
             commands.trigger_targets(
                 RotateTankTurretCommandTrigger {
                     sender: None,
+                    tick_sent: 0,
                     message: RotateTankTurretCommand {
                         yaw_angle: tank_config.turret_yaw_rotation_speed,
                         pitch_angle: 0.0,
@@ -91,6 +94,7 @@ pub fn simulate_movement(
                 commands.trigger_targets(
                     MoveTankCommandTrigger {
                         sender: None,
+                        tick_sent: 0,
                         message: MoveTankCommand {
                             distance: tank_config.move_speed * direction,
                         },
@@ -105,6 +109,7 @@ pub fn simulate_movement(
                 commands.trigger_targets(
                     RotateTankBodyCommandTrigger {
                         sender: None,
+                        tick_sent: 0,
                         message: RotateTankBodyCommand {
                             angle: tank_config.body_rotation_speed * direction,
                         },
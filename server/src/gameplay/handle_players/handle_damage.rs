@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+use shared::{
+    asset_handling::config::{ModuleCriticalConfig, TankConfigSystemParam},
+    game::{
+        collision_handling::triggers::FellFromLedgeTrigger,
+        player_handling::{Health, PlayerState, ShootCooldown, TankModules, TankRepair},
+        tank_types::TankType,
+    },
+    networking::lobby_management::MyLobby,
+};
+
+use crate::gameplay::triggers::StartNextTickProcessingTrigger;
+
+#[derive(Debug, Reflect, Event)]
+pub struct TakeDamageTrigger {
+    pub damage: f32,
+}
+
+/// Sent to a tank that got hit by a projectile
+#[derive(Debug, Reflect, Event)]
+pub struct HitByProjectileTrigger {
+    pub damage: f32,
+}
+
+pub fn take_damage(
+    trigger: Trigger<TakeDamageTrigger>,
+    mut tank: Query<(&mut Health, &mut PlayerState, &mut TankRepair)>,
+) {
+    let client_entity = trigger.entity();
+    let (mut health, mut player_state, mut repair) = tank
+        .get_mut(client_entity)
+        .expect("Failed to get tank health");
+
+    if *player_state == PlayerState::Dead {
+        return;
+    }
+
+    repair.enter_combat();
+    health.health = (health.health - trigger.damage).max(0.0);
+    if health.is_dead() {
+        info!("Player {:?} died", client_entity);
+        *player_state = PlayerState::Dead;
+    }
+}
+
+pub fn handle_fall_damage(
+    trigger: Trigger<FellFromLedgeTrigger>,
+    tank: Query<&TankType>,
+    tank_config: TankConfigSystemParam,
+    mut commands: Commands,
+) {
+    let client_entity = trigger.entity();
+    let tank_type = tank.get(client_entity).expect("Failed to get tank type");
+    let tank_config = tank_config
+        .get_tank_type_config(tank_type)
+        .expect("Failed to get tank config");
+
+    let damage = tank_config.fall_damage_for_height(trigger.height);
+    if damage > 0.0 {
+        commands.trigger_targets(TakeDamageTrigger { damage }, client_entity);
+    }
+}
+
+/// Applies the damage of a projectile hit and rolls for critical hits on the modules of the tank
+pub fn handle_projectile_hit(
+    trigger: Trigger<HitByProjectileTrigger>,
+    mut tank: Query<(
+        &TankType,
+        &PlayerState,
+        &mut TankModules,
+        &mut ShootCooldown,
+    )>,
+    tank_config: TankConfigSystemParam,
+    mut commands: Commands,
+) {
+    let client_entity = trigger.entity();
+    let (tank_type, player_state, mut modules, mut shoot_cooldown) = tank
+        .get_mut(client_entity)
+        .expect("Failed to get tank modules");
+
+    if *player_state == PlayerState::Dead {
+        return;
+    }
+
+    let tank_config = tank_config
+        .get_tank_type_config(tank_type)
+        .expect("Failed to get tank config");
+
+    commands.trigger_targets(
+        TakeDamageTrigger {
+            damage: trigger.damage,
+        },
+        client_entity,
+    );
+
+    if roll_critical(&tank_config.track_critical) {
+        info!("Player {:?} got its tracks destroyed", client_entity);
+        modules.tracks_disabled_ticks = modules
+            .tracks_disabled_ticks
+            .max(tank_config.track_critical.disabled_ticks);
+    }
+
+    if roll_critical(&tank_config.turret_ring_critical) {
+        info!("Player {:?} got its turret ring jammed", client_entity);
+        modules.turret_ring_disabled_ticks = modules
+            .turret_ring_disabled_ticks
+            .max(tank_config.turret_ring_critical.disabled_ticks);
+    }
+
+    if roll_critical(&tank_config.gun_critical) {
+        info!("Player {:?} got its gun damaged", client_entity);
+        shoot_cooldown.ticks_left += tank_config.gun_critical.disabled_ticks;
+    }
+}
+
+fn roll_critical(critical: &ModuleCriticalConfig) -> bool {
+    critical.chance > 0.0 && rand::random::<f32>() < critical.chance
+}
+
+pub fn tick_module_repairs(
+    trigger: Trigger<StartNextTickProcessingTrigger>,
+    lobby: Query<&MyLobby>,
+    mut modules: Query<&mut TankModules>,
+) {
+    let lobby_entity = trigger.entity();
+    let lobby = lobby.get(lobby_entity).expect("Failed to get lobby");
+
+    for (_, player, _) in lobby.players.iter() {
+        if let Ok(mut modules) = modules.get_mut(*player) {
+            modules.tick();
+        }
+    }
+}
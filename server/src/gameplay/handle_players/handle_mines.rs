@@ -0,0 +1,139 @@
+use bevy::{ecs::entity::EntityHashSet, prelude::*};
+use shared::{
+    asset_handling::config::TankConfigSystemParam,
+    game::{
+        collision_handling::{components::CollisionLayer, triggers::CollidedWithTrigger},
+        mine_handling::{MineMarker, MINE_SIZE},
+        player_handling::{DeployedMines, PlayerState, TankBodyMarker},
+        tank_types::TankType,
+    },
+    networking::{
+        lobby_management::{InLobby, InTeam, MyLobby},
+        messages::{
+            message_container::DeployMineCommandTrigger,
+            message_data::command_acknowledgement::{AcknowledgedCommand, CommandRejectionReason},
+        },
+    },
+};
+
+use super::{command_acknowledgements::CommandProcessedTrigger, handle_damage::TakeDamageTrigger};
+
+pub fn handle_deploy_mine_command(
+    trigger: Trigger<DeployMineCommandTrigger>,
+    mut lobby: Query<&mut MyLobby>,
+    mut body: Query<(
+        &Transform,
+        &TankType,
+        &PlayerState,
+        &mut DeployedMines,
+        &InLobby,
+    )>,
+    tank_config: TankConfigSystemParam,
+    mut commands: Commands,
+) {
+    let client_entity = trigger.entity();
+    let (tank_transform, tank_type, player_state, mut deployed_mines, in_lobby) = body
+        .get_mut(client_entity)
+        .expect("Failed to get tank transform");
+    let tank_config = tank_config
+        .get_tank_type_config(tank_type)
+        .expect("Failed to get tank config");
+
+    let command = AcknowledgedCommand::DeployMineCommand(trigger.message.clone());
+
+    let rejection_reason = if *player_state == PlayerState::Dead {
+        Some(CommandRejectionReason::Dead)
+    } else if deployed_mines.count >= tank_config.mine_allowance {
+        Some(CommandRejectionReason::NoMinesLeft)
+    } else {
+        None
+    };
+    if let Some(rejection_reason) = rejection_reason {
+        commands.trigger_targets(
+            CommandProcessedTrigger::rejected(command, trigger.tick_sent, rejection_reason),
+            client_entity,
+        );
+        return;
+    }
+
+    let mut lobby = lobby.get_mut(in_lobby.0).expect("Failed to get lobby");
+    let map = &lobby
+        .map_config
+        .as_ref()
+        .expect("Failed to get map config")
+        .map;
+
+    // Drop the mine right behind the tank, on the floor
+    let behind = tank_transform.rotation * Vec3::NEG_Z;
+    let mut mine_position =
+        tank_transform.translation + behind * (tank_config.size.z / 2.0 + MINE_SIZE.z);
+    if let Some(floor_height) = map.get_floor_height_at_position(mine_position) {
+        mine_position.y = floor_height + MINE_SIZE.y / 2.0;
+    }
+
+    let mine = commands
+        .spawn((
+            Name::new("Mine"),
+            Transform::from_translation(mine_position).with_rotation(tank_transform.rotation),
+            MineMarker {
+                owner: client_entity,
+                damage: tank_config.mine_damage,
+            },
+            CollisionLayer::new(&[0]).with_ignore(EntityHashSet::from_iter(vec![client_entity])),
+            in_lobby.clone(),
+        ))
+        .id();
+
+    lobby.mines.push(mine);
+
+    deployed_mines.count += 1;
+
+    commands.trigger_targets(
+        CommandProcessedTrigger::applied(command, trigger.tick_sent),
+        client_entity,
+    );
+}
+
+/// Detonates the mine when an enemy tank drives over it
+pub fn detonate_on_enemy_contact(
+    trigger: Trigger<CollidedWithTrigger>,
+    mines: Query<(&MineMarker, &InLobby)>,
+    tanks: Query<(&InTeam, &PlayerState), With<TankBodyMarker>>,
+    mut lobbies: Query<&mut MyLobby>,
+    mut commands: Commands,
+) {
+    let mine_entity = trigger.entity();
+    let (mine, in_lobby) = mines.get(mine_entity).expect("Failed to get mine");
+    let collided_with = trigger.event().entity;
+
+    if let Ok((in_team, player_state)) = tanks.get(collided_with) {
+        if *player_state == PlayerState::Dead {
+            return;
+        }
+
+        // Mines of players that already left the lobby are dangerous to everyone
+        let is_teammate = tanks
+            .get(mine.owner)
+            .map(|(owner_team, _)| owner_team.0 == in_team.0)
+            .unwrap_or(false);
+        if is_teammate {
+            return;
+        }
+
+        let mut lobby = lobbies.get_mut(**in_lobby).expect("Failed to get lobby");
+        // Another tank touching the mine in the same tick already detonated it
+        if !lobby.mines.contains(&mine_entity) {
+            return;
+        }
+        lobby.remove_mine(mine_entity);
+
+        commands.trigger_targets(
+            TakeDamageTrigger {
+                damage: mine.damage,
+            },
+            collided_with,
+        );
+
+        commands.entity(mine_entity).despawn_recursive();
+    }
+}
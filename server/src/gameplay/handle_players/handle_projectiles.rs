@@ -12,9 +12,12 @@ use shared::{
     networking::lobby_management::MyLobby,
 };
 
-use crate::gameplay::triggers::{
-    FinishedNextSimulationStepTrigger, StartNextSimulationStepTrigger,
-    StartNextTickProcessingTrigger,
+use crate::gameplay::{
+    handle_players::handle_damage::HitByProjectileTrigger,
+    triggers::{
+        FinishedNextSimulationStepTrigger, StartNextSimulationStepTrigger,
+        StartNextTickProcessingTrigger,
+    },
 };
 
 pub fn colliding_with_entity(
@@ -29,8 +32,13 @@ pub fn colliding_with_entity(
         .expect("Failed to get projectile");
     let collided_with = trigger.event().entity;
 
-    if let Ok(body) = players.get(collided_with) {
-        println!("Projectile hit player: {:?}", body);
+    if players.get(collided_with).is_ok() {
+        commands.trigger_targets(
+            HitByProjectileTrigger {
+                damage: projectile.damage,
+            },
+            collided_with,
+        );
 
         commands.entity(projectile_entity).despawn_recursive();
     }
@@ -0,0 +1,82 @@
+use bevy::prelude::*;
+use shared::{
+    asset_handling::config::TankConfigSystemParam,
+    game::{
+        player_handling::{Health, PlayerState, TankRepair},
+        tank_types::TankType,
+    },
+    networking::{
+        lobby_management::MyLobby,
+        messages::{
+            message_container::RepairCommandTrigger,
+            message_data::command_acknowledgement::{AcknowledgedCommand, CommandRejectionReason},
+        },
+    },
+};
+
+use crate::gameplay::{
+    handle_players::command_acknowledgements::CommandProcessedTrigger,
+    triggers::StartNextTickProcessingTrigger,
+};
+
+pub fn handle_repair_command(
+    trigger: Trigger<RepairCommandTrigger>,
+    mut tank: Query<(&mut TankRepair, &PlayerState)>,
+    mut commands: Commands,
+) {
+    let client_entity = trigger.entity();
+    let (mut repair, player_state) = tank
+        .get_mut(client_entity)
+        .expect("Failed to get tank repair");
+    let command = AcknowledgedCommand::RepairCommand(trigger.message.clone());
+
+    if *player_state == PlayerState::Dead {
+        commands.trigger_targets(
+            CommandProcessedTrigger::rejected(
+                command,
+                trigger.tick_sent,
+                CommandRejectionReason::Dead,
+            ),
+            client_entity,
+        );
+        return;
+    }
+
+    repair.repairing = trigger.active;
+    commands.trigger_targets(
+        CommandProcessedTrigger::applied(command, trigger.tick_sent),
+        client_entity,
+    );
+}
+
+/// Heals all tanks that are actively repairing or have been out of combat long enough
+pub fn repair_tanks(
+    trigger: Trigger<StartNextTickProcessingTrigger>,
+    lobby: Query<&MyLobby>,
+    mut tanks: Query<(&mut Health, &mut TankRepair, &PlayerState, &TankType)>,
+    tank_config: TankConfigSystemParam,
+) {
+    let lobby_entity = trigger.entity();
+    let lobby = lobby.get(lobby_entity).expect("Failed to get lobby");
+
+    for (_, player, _) in lobby.players.iter() {
+        if let Ok((mut health, mut repair, player_state, tank_type)) = tanks.get_mut(*player) {
+            if *player_state == PlayerState::Dead {
+                repair.repairing = false;
+                continue;
+            }
+
+            let tank_config = tank_config
+                .get_tank_type_config(tank_type)
+                .expect("Failed to get tank config");
+
+            repair.ticks_out_of_combat = repair.ticks_out_of_combat.saturating_add(1);
+
+            if repair.repairing {
+                health.heal(tank_config.active_repair_rate);
+            } else if repair.ticks_out_of_combat > tank_config.repair_delay {
+                health.heal(tank_config.repair_rate);
+            }
+        }
+    }
+}
@@ -3,68 +3,109 @@ use shared::{
     asset_handling::config::TankConfigSystemParam,
     game::{
         collision_handling::components::{CollisionLayer, WantedTransform},
-        player_handling::{ShootCooldown, TankBodyMarker, TankTurretMarker},
+        player_handling::{
+            PlayerState, ShootCooldown, TankBodyMarker, TankRepair, TankTurretMarker,
+        },
         projectile_handling::ProjectileMarker,
         tank_types::TankType,
     },
     networking::{
         lobby_management::{InLobby, MyLobby},
-        messages::message_container::ShootCommandTrigger,
+        messages::{
+            message_container::ShootCommandTrigger,
+            message_data::command_acknowledgement::{AcknowledgedCommand, CommandRejectionReason},
+        },
     },
 };
 
-use crate::gameplay::triggers::StartNextTickProcessingTrigger;
+use crate::gameplay::{
+    handle_players::command_acknowledgements::CommandProcessedTrigger,
+    triggers::StartNextTickProcessingTrigger,
+};
 
 pub fn handle_tank_shooting_command(
     trigger: Trigger<ShootCommandTrigger>,
     mut lobby: Query<&mut MyLobby>,
-    mut body: Query<(&TankType, &mut ShootCooldown, &TankBodyMarker, &InLobby)>,
+    mut body: Query<(
+        &TankType,
+        &mut ShootCooldown,
+        &mut TankRepair,
+        &TankBodyMarker,
+        &PlayerState,
+        &InLobby,
+    )>,
     turret_transform: Query<&GlobalTransform, With<TankTurretMarker>>,
     tank_config: TankConfigSystemParam,
     mut commands: Commands,
 ) {
     let client_entity = trigger.entity();
-    let (tank_type, mut cooldown, tank_body, in_lobby) = body
+    let (tank_type, mut cooldown, mut repair, tank_body, player_state, in_lobby) = body
         .get_mut(client_entity)
         .expect("Failed to get tank transform");
 
-    if cooldown.ticks_left <= 0 {
-        let tank_config = tank_config
-            .get_tank_type_config(tank_type)
-            .expect("Failed to get tank config");
+    let rejection_reason = if *player_state == PlayerState::Dead {
+        Some(CommandRejectionReason::Dead)
+    } else if cooldown.ticks_left > 0 {
+        Some(CommandRejectionReason::OnCooldown)
+    } else {
+        None
+    };
+    if let Some(rejection_reason) = rejection_reason {
+        commands.trigger_targets(
+            CommandProcessedTrigger::rejected(
+                AcknowledgedCommand::ShootCommand(trigger.message.clone()),
+                trigger.tick_sent,
+                rejection_reason,
+            ),
+            client_entity,
+        );
+        return;
+    }
 
-        let mut lobby = lobby.get_mut(in_lobby.0).expect("Failed to get lobby");
+    let tank_config = tank_config
+        .get_tank_type_config(tank_type)
+        .expect("Failed to get tank config");
 
-        let turret_entity = tank_body.turret.expect("Failed to get turret entity");
-        let turret_transform = turret_transform
-            .get(turret_entity)
-            .expect("Failed to get turret transform");
+    let mut lobby = lobby.get_mut(in_lobby.0).expect("Failed to get lobby");
 
-        let bullet_spawn_position = turret_transform.translation();
-        let bullet_spawn_rotation = turret_transform.rotation();
+    let turret_entity = tank_body.turret.expect("Failed to get turret entity");
+    let turret_transform = turret_transform
+        .get(turret_entity)
+        .expect("Failed to get turret transform");
 
-        let transform =
-            Transform::from_translation(bullet_spawn_position).with_rotation(bullet_spawn_rotation);
-        let bullet = commands
-            .spawn((
-                Name::new("Projectile"),
-                WantedTransform(transform),
-                transform,
-                ProjectileMarker {
-                    owner: client_entity,
-                    damage: tank_config.projectile_damage,
-                    speed: tank_config.projectile_speed,
-                },
-                CollisionLayer::new(&[0])
-                    .with_ignore(EntityHashSet::from_iter(vec![client_entity, turret_entity])),
-                in_lobby.clone(),
-            ))
-            .id();
+    let bullet_spawn_position = turret_transform.translation();
+    let bullet_spawn_rotation = turret_transform.rotation();
 
-        lobby.projectiles.push(bullet);
+    let transform =
+        Transform::from_translation(bullet_spawn_position).with_rotation(bullet_spawn_rotation);
+    let bullet = commands
+        .spawn((
+            Name::new("Projectile"),
+            WantedTransform(transform),
+            transform,
+            ProjectileMarker {
+                owner: client_entity,
+                damage: tank_config.projectile_damage,
+                speed: tank_config.projectile_speed,
+            },
+            CollisionLayer::new(&[0])
+                .with_ignore(EntityHashSet::from_iter(vec![client_entity, turret_entity])),
+            in_lobby.clone(),
+        ))
+        .id();
 
-        cooldown.ticks_left = cooldown.ticks_cooldown;
-    }
+    lobby.projectiles.push(bullet);
+
+    cooldown.ticks_left = cooldown.ticks_cooldown;
+    repair.enter_combat();
+
+    commands.trigger_targets(
+        CommandProcessedTrigger::applied(
+            AcknowledgedCommand::ShootCommand(trigger.message.clone()),
+            trigger.tick_sent,
+        ),
+        client_entity,
+    );
 }
 
 pub fn tick_shoot_cooldowns(
@@ -3,7 +3,10 @@ use shared::{
     asset_handling::config::TankConfigSystemParam,
     game::{
         collision_handling::components::WantedTransform,
-        player_handling::{PlayerState, TankBodyMarker, TankTurretMarker},
+        player_handling::{
+            DeployedMines, Health, PlayerState, TankBodyMarker, TankModules, TankMomentum,
+            TankRepair, TankTurretMarker,
+        },
         tank_types::TankType,
     },
     networking::lobby_management::{lobby_management::LobbyManagementSystemParam, InLobby, InTeam},
@@ -21,6 +24,11 @@ pub fn respawn_player(
         &mut Transform,
         &mut WantedTransform,
         &mut PlayerState,
+        &mut Health,
+        &mut TankMomentum,
+        &mut TankModules,
+        &mut TankRepair,
+        &mut DeployedMines,
         &MyNetworkClient,
         &InTeam,
         &InLobby,
@@ -36,6 +44,11 @@ pub fn respawn_player(
         mut tank_transform,
         mut wanted_transform,
         mut player_state,
+        mut health,
+        mut momentum,
+        mut modules,
+        mut repair,
+        mut deployed_mines,
         client,
         client_team,
         client_in_lobby,
@@ -44,6 +57,11 @@ pub fn respawn_player(
     )) = body_query.get_mut(client_entity)
     {
         *player_state = PlayerState::Alive;
+        health.health = health.max_health;
+        momentum.stop();
+        *modules = TankModules::default();
+        *repair = TankRepair::default();
+        *deployed_mines = DeployedMines::default();
 
         let lobby = lobby_management
             .get_lobby(client_in_lobby.0)
@@ -1,14 +1,21 @@
 use bevy::prelude::*;
 use dummy_handling::DummyClientMarker;
 use shared::{
-    game::{player_handling::TankBodyMarker, projectile_handling::ProjectileMarker},
+    game::{
+        mine_handling::MineMarker, player_handling::TankBodyMarker,
+        projectile_handling::ProjectileMarker,
+    },
     networking::lobby_management::MyLobby,
 };
 
 use crate::networking::handle_clients::lib::MyNetworkClient;
 
+pub mod command_acknowledgements;
 pub mod dummy_handling;
+pub mod handle_damage;
+pub mod handle_mines;
 pub mod handle_projectiles;
+pub mod handle_repair;
 pub mod handle_shooting;
 pub mod handle_spawning;
 pub mod insert_turret;
@@ -25,6 +32,7 @@ impl Plugin for HandlePlayersPlugin {
             .add_observer(add_observers_to_lobby)
             .add_observer(add_observers_to_client)
             .add_observer(add_observers_to_projectile)
+            .add_observer(add_observers_to_mine)
             .add_observer(insert_turret::insert_turret)
             .add_observer(dummy_handling::add_observers_to_dummies)
             .add_observer(dummy_handling::add_dummy_simulation_observers_to_lobby);
@@ -32,13 +40,22 @@ impl Plugin for HandlePlayersPlugin {
 }
 
 /// Add observers to ALL clients (players and spectators)
-fn add_observers_to_client(_trigger: Trigger<OnAdd, MyNetworkClient>, mut _commands: Commands) {}
+fn add_observers_to_client(trigger: Trigger<OnAdd, MyNetworkClient>, mut commands: Commands) {
+    commands
+        .entity(trigger.entity())
+        .observe(command_acknowledgements::record_command_acknowledgement);
+}
 
 /// Add observers to ALL players (excluding spectators)
 fn add_observers_to_player(trigger: Trigger<OnAdd, TankBodyMarker>, mut commands: Commands) {
     commands
         .entity(trigger.entity())
         .observe(handle_spawning::respawn_player)
+        .observe(handle_damage::take_damage)
+        .observe(handle_damage::handle_fall_damage)
+        .observe(handle_damage::handle_projectile_hit)
+        .observe(handle_repair::handle_repair_command)
+        .observe(handle_mines::handle_deploy_mine_command)
         .observe(update_client_states::update_client_states)
         .observe(handle_shooting::handle_tank_shooting_command);
 }
@@ -47,6 +64,8 @@ fn add_observers_to_lobby(trigger: Trigger<OnAdd, MyLobby>, mut commands: Comman
     commands
         .entity(trigger.entity())
         .observe(handle_shooting::tick_shoot_cooldowns)
+        .observe(handle_damage::tick_module_repairs)
+        .observe(handle_repair::repair_tanks)
         .observe(handle_projectiles::move_projectiles)
         .observe(handle_projectiles::handle_despawn_timer)
         .observe(handle_projectiles::despawn_out_of_bounds);
@@ -58,3 +77,9 @@ fn add_observers_to_projectile(trigger: Trigger<OnAdd, ProjectileMarker>, mut co
         .observe(handle_projectiles::despawn_on_collision_with_world)
         .observe(handle_projectiles::colliding_with_entity);
 }
+
+fn add_observers_to_mine(trigger: Trigger<OnAdd, MineMarker>, mut commands: Commands) {
+    commands
+        .entity(trigger.entity())
+        .observe(handle_mines::detonate_on_enemy_contact);
+}
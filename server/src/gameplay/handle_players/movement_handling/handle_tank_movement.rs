@@ -1,25 +1,175 @@
 use bevy::prelude::*;
 use shared::{
-    asset_handling::config::TankConfigSystemParam,
-    game::{collision_handling::components::WantedTransform, tank_types::TankType},
-    networking::messages::message_container::MoveTankCommandTrigger,
+    asset_handling::{
+        config::{TankConfig, TankConfigSystemParam},
+        maps::MapDefinition,
+    },
+    game::{
+        collision_handling::{components::WantedTransform, triggers::CollidedWithWorldTrigger},
+        player_handling::{PlayerState, TankModules, TankMomentum, TankRepair},
+        tank_types::TankType,
+    },
+    networking::{
+        lobby_management::{InLobby, MyLobby},
+        messages::{
+            message_container::MoveTankCommandTrigger,
+            message_data::{
+                command_acknowledgement::{AcknowledgedCommand, CommandRejectionReason},
+                tank_messages::move_tank::MoveTankCommand,
+            },
+        },
+    },
+};
+
+use crate::gameplay::{
+    handle_players::command_acknowledgements::CommandProcessedTrigger,
+    triggers::StartNextSimulationStepTrigger,
 };
 
 pub fn handle_tank_movement(
     trigger: Trigger<MoveTankCommandTrigger>,
-    mut tank: Query<(&mut WantedTransform, &TankType)>,
+    mut tank: Query<(
+        &mut WantedTransform,
+        &mut TankMomentum,
+        &TankModules,
+        &TankRepair,
+        &TankType,
+        &PlayerState,
+        &InLobby,
+    )>,
+    lobby: Query<&MyLobby>,
     tank_config: TankConfigSystemParam,
+    mut commands: Commands,
 ) {
     let client_entity = trigger.entity();
-    let (mut tank_transform, tank_type) = tank
-        .get_mut(client_entity)
-        .expect("Failed to get tank transform");
+    let (mut tank_transform, mut momentum, modules, repair, tank_type, player_state, in_lobby) =
+        tank.get_mut(client_entity)
+            .expect("Failed to get tank transform");
+
+    let rejection_reason = if *player_state == PlayerState::Dead {
+        Some(CommandRejectionReason::Dead)
+    } else if !modules.tracks_working() || repair.repairing {
+        // Broken tracks and repairing immobilize the tank
+        Some(CommandRejectionReason::Immobilized)
+    } else {
+        None
+    };
+    if let Some(rejection_reason) = rejection_reason {
+        commands.trigger_targets(
+            CommandProcessedTrigger::rejected(
+                AcknowledgedCommand::MoveTankCommand(trigger.message.clone()),
+                trigger.tick_sent,
+                rejection_reason,
+            ),
+            client_entity,
+        );
+        return;
+    }
+
     let tank_config = tank_config
         .get_tank_type_config(tank_type)
         .expect("Failed to get tank config");
-    let distance = trigger
-        .distance
-        .clamp(-tank_config.move_speed, tank_config.move_speed);
+
+    let distance = tank_config.clamp_speed(trigger.distance);
+    commands.trigger_targets(
+        CommandProcessedTrigger::applied(
+            AcknowledgedCommand::MoveTankCommand(MoveTankCommand { distance }),
+            trigger.tick_sent,
+        ),
+        client_entity,
+    );
+
+    // Tanks with momentum only set their throttle, they are moved each tick in apply_tank_momentum
+    if tank_config.acceleration.is_some() {
+        momentum.throttle = distance;
+        return;
+    }
+
+    let map = &lobby
+        .get(in_lobby.0)
+        .expect("Failed to get lobby")
+        .map_config
+        .as_ref()
+        .expect("Failed to get map config")
+        .map;
+
+    move_tank_forward(&mut tank_transform, distance, map, tank_config);
+}
+
+/// Moves all tanks with momentum by their current speed, after accelerating or braking towards their throttle
+pub fn apply_tank_momentum(
+    trigger: Trigger<StartNextSimulationStepTrigger>,
+    lobby: Query<&MyLobby>,
+    mut tanks: Query<(
+        &mut WantedTransform,
+        &mut TankMomentum,
+        &TankModules,
+        &TankRepair,
+        &TankType,
+        &PlayerState,
+    )>,
+    tank_config: TankConfigSystemParam,
+) {
+    let lobby = lobby.get(trigger.entity()).expect("Failed to get lobby");
+    let map = &lobby
+        .map_config
+        .as_ref()
+        .expect("Failed to get map config")
+        .map;
+
+    for (_, player, _) in lobby.players.iter() {
+        if let Ok((mut tank_transform, mut momentum, modules, repair, tank_type, player_state)) =
+            tanks.get_mut(*player)
+        {
+            // Dead tanks don't drive on, not even after respawning
+            if *player_state == PlayerState::Dead || !modules.tracks_working() || repair.repairing {
+                momentum.stop();
+                continue;
+            }
+
+            let tank_config = tank_config
+                .get_tank_type_config(tank_type)
+                .expect("Failed to get tank config");
+
+            if let Some(acceleration) = tank_config.acceleration {
+                let deceleration = tank_config.deceleration.unwrap_or(acceleration);
+                momentum.update_speed(acceleration, deceleration);
+
+                move_tank_forward(&mut tank_transform, momentum.speed, map, tank_config);
+            }
+        }
+    }
+}
+
+/// A tank driving into a wall loses its speed, but keeps its throttle
+pub fn stop_on_collision_with_world(
+    trigger: Trigger<CollidedWithWorldTrigger>,
+    mut momentum: Query<&mut TankMomentum>,
+) {
+    if let Ok(mut momentum) = momentum.get_mut(trigger.entity()) {
+        momentum.speed = 0.0;
+    }
+}
+
+fn move_tank_forward(
+    tank_transform: &mut WantedTransform,
+    distance: f32,
+    map: &MapDefinition,
+    tank_config: &TankConfig,
+) {
+    if distance == 0.0 {
+        return;
+    }
+
+    // Driving uphill slows the tank down, driving downhill speeds it up
+    let slope = map
+        .get_slope_in_direction(
+            tank_transform.translation,
+            tank_transform.rotation * Vec3::Z * distance.signum(),
+        )
+        .unwrap_or(0.0);
+    let distance = distance * tank_config.slope_speed_multiplier(slope);
+
     let move_direction = tank_transform.rotation * Vec3::new(0.0, 0.0, distance);
     let next_tank_position = tank_transform.translation + move_direction;
 
@@ -1,19 +1,49 @@
 use bevy::prelude::*;
 use shared::{
     asset_handling::config::TankConfigSystemParam,
-    game::{collision_handling::components::WantedTransform, player_handling::TankBodyMarker, tank_types::TankType},
-    networking::messages::message_container::RotateTankBodyCommandTrigger,
+    game::{collision_handling::components::WantedTransform, player_handling::{PlayerState, TankBodyMarker, TankModules, TankRepair}, tank_types::TankType},
+    networking::messages::{
+        message_container::RotateTankBodyCommandTrigger,
+        message_data::{
+            command_acknowledgement::{AcknowledgedCommand, CommandRejectionReason},
+            tank_messages::rotate_tank_body::RotateTankBodyCommand,
+        },
+    },
 };
 
+use crate::gameplay::handle_players::command_acknowledgements::CommandProcessedTrigger;
+
 pub fn handle_tank_body_rotation(
     trigger: Trigger<RotateTankBodyCommandTrigger>,
-    mut body_transform: Query<(&mut WantedTransform, &TankType), With<TankBodyMarker>>,
+    mut body_transform: Query<(&mut WantedTransform, &TankModules, &TankRepair, &TankType, &PlayerState), With<TankBodyMarker>>,
     tank_config: TankConfigSystemParam,
+    mut commands: Commands,
 ) {
     let client_entity = trigger.entity();
-    let (mut tank_transform, tank_type) = body_transform
+    let (mut tank_transform, modules, repair, tank_type, player_state) = body_transform
         .get_mut(client_entity)
         .expect("Failed to get tank transform");
+
+    let rejection_reason = if *player_state == PlayerState::Dead {
+        Some(CommandRejectionReason::Dead)
+    } else if !modules.tracks_working() || repair.repairing {
+        // Broken tracks and repairing immobilize the tank
+        Some(CommandRejectionReason::Immobilized)
+    } else {
+        None
+    };
+    if let Some(rejection_reason) = rejection_reason {
+        commands.trigger_targets(
+            CommandProcessedTrigger::rejected(
+                AcknowledgedCommand::RotateTankBodyCommand(trigger.message.clone()),
+                trigger.tick_sent,
+                rejection_reason,
+            ),
+            client_entity,
+        );
+        return;
+    }
+
     let tank_config = tank_config
         .get_tank_type_config(tank_type)
         .expect("Failed to get tank config");
@@ -24,4 +54,12 @@ pub fn handle_tank_body_rotation(
     );
 
     tank_transform.rotation *= Quat::from_rotation_y(rotation);
+
+    commands.trigger_targets(
+        CommandProcessedTrigger::applied(
+            AcknowledgedCommand::RotateTankBodyCommand(RotateTankBodyCommand { angle: rotation }),
+            trigger.tick_sent,
+        ),
+        client_entity,
+    );
 }
@@ -2,22 +2,55 @@ use bevy::prelude::*;
 use shared::{
     asset_handling::config::TankConfigSystemParam,
     game::{
-        player_handling::{TankBodyMarker, TankTurretMarker},
+        player_handling::{PlayerState, TankBodyMarker, TankModules, TankTurretMarker},
         tank_types::TankType,
     },
-    networking::messages::message_container::RotateTankTurretCommandTrigger,
+    networking::messages::{
+        message_container::RotateTankTurretCommandTrigger,
+        message_data::{
+            command_acknowledgement::{AcknowledgedCommand, CommandRejectionReason},
+            tank_messages::rotate_tank_turret::RotateTankTurretCommand,
+        },
+    },
 };
 
+use crate::gameplay::handle_players::command_acknowledgements::CommandProcessedTrigger;
+
 pub fn handle_tank_turret_rotation(
     trigger: Trigger<RotateTankTurretCommandTrigger>,
-    body: Query<(&TankType, &TankBodyMarker), Without<TankTurretMarker>>,
+    body: Query<
+        (&TankType, &TankBodyMarker, &TankModules, &PlayerState),
+        Without<TankTurretMarker>,
+    >,
     mut turret_transform: Query<&mut Transform, With<TankTurretMarker>>,
     tank_config: TankConfigSystemParam,
+    mut commands: Commands,
 ) {
     let client_entity = trigger.entity();
-    let (tank_type, tank_body) = body
+    let (tank_type, tank_body, modules, player_state) = body
         .get(client_entity)
         .expect("Failed to get tank transform");
+
+    let rejection_reason = if *player_state == PlayerState::Dead {
+        Some(CommandRejectionReason::Dead)
+    } else if !modules.turret_ring_working() {
+        // A jammed turret ring stops the turret from rotating
+        Some(CommandRejectionReason::TurretJammed)
+    } else {
+        None
+    };
+    if let Some(rejection_reason) = rejection_reason {
+        commands.trigger_targets(
+            CommandProcessedTrigger::rejected(
+                AcknowledgedCommand::RotateTankTurretCommand(trigger.message.clone()),
+                trigger.tick_sent,
+                rejection_reason,
+            ),
+            client_entity,
+        );
+        return;
+    }
+
     let tank_config = tank_config
         .get_tank_type_config(tank_type)
         .expect("Failed to get tank config");
@@ -55,4 +88,16 @@ pub fn handle_tank_turret_rotation(
 
     // Construct a new rotation with roll forcibly set to zero.
     turret_transform.rotation = Quat::from_euler(EulerRot::YXZ, new_yaw, new_pitch, 0.0);
+
+    // The pitch might have been limited further by the turret's maximum and minimum pitch
+    commands.trigger_targets(
+        CommandProcessedTrigger::applied(
+            AcknowledgedCommand::RotateTankTurretCommand(RotateTankTurretCommand {
+                yaw_angle: yaw_delta,
+                pitch_angle: new_pitch - current_pitch,
+            }),
+            trigger.tick_sent,
+        ),
+        client_entity,
+    );
 }
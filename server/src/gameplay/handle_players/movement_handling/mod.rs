@@ -20,8 +20,13 @@ fn add_observers_to_client(trigger: Trigger<OnAdd, MyNetworkClient>, mut command
     commands
         .entity(trigger.entity())
         .observe(handle_tank_movement::handle_tank_movement)
+        .observe(handle_tank_movement::stop_on_collision_with_world)
         .observe(handle_tank_rotation::handle_tank_body_rotation)
         .observe(handle_turret_rotation::handle_tank_turret_rotation);
 }
 
-fn add_observers_to_lobby(_trigger: Trigger<OnAdd, MyLobby>, mut _commands: Commands) {}
+fn add_observers_to_lobby(trigger: Trigger<OnAdd, MyLobby>, mut commands: Commands) {
+    commands
+        .entity(trigger.entity())
+        .observe(handle_tank_movement::apply_tank_momentum);
+}
@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use shared::{
-    game::game_state::PersonalizedClientGameState,
+    asset_handling::config::TankConfigSystemParam,
+    game::{game_state::PersonalizedClientGameState, tank_types::TankType},
     networking::lobby_management::{lobby_management::LobbyManagementSystemParam, InLobby, InTeam},
 };
 
@@ -11,11 +12,13 @@ use crate::gameplay::triggers::UpdateClientGameStatesTrigger;
 pub fn update_client_states(
     trigger: Trigger<UpdateClientGameStatesTrigger>,
     lobby_management: LobbyManagementSystemParam,
-    clients: Query<(&InTeam, &InLobby)>,
+    clients: Query<(&InTeam, &InLobby, &Transform, &TankType)>,
     mut states: Query<&mut PersonalizedClientGameState>,
+    tank_config: TankConfigSystemParam,
 ) {
     let client_entity = trigger.entity();
-    let (in_team, in_lobby) = clients.get(client_entity).expect("Failed to get in team");
+    let (in_team, in_lobby, tank_transform, tank_type) =
+        clients.get(client_entity).expect("Failed to get in team");
     let mut client_state = states
         .get_mut(client_entity)
         .expect("Failed to get client state");
@@ -63,9 +66,12 @@ pub fn update_client_states(
             .iter()
             .for_each(|(state_entity, state)| {
                 if state_entity == *entity {
+                    // The module state is only known to the client itself
+                    let mut state = state.clone();
+                    state.modules = None;
                     client_state
                         .other_client_states
-                        .insert(**entity, Some(state.clone()));
+                        .insert(**entity, Some(state));
                     return;
                 }
             });
@@ -79,14 +85,33 @@ pub fn update_client_states(
             .iter()
             .for_each(|(state_entity, state)| {
                 if state_entity == entity {
+                    let mut state = state.clone();
+                    state.modules = None;
                     client_state
                         .other_client_states
-                        .insert(*entity, Some(state.clone()));
+                        .insert(*entity, Some(state));
                     return;
                 }
             });
     });
 
+    // Adding our team's mines, and the enemy mines that are close enough to be spotted
+    let mine_detection_range = tank_config
+        .get_tank_type_config(tank_type)
+        .expect("Failed to get tank config")
+        .mine_detection_range;
+    lobby_state.mines.iter().for_each(|(entity, mine)| {
+        let is_friendly = mine.owner_id == client_entity || team_players.contains(&&mine.owner_id);
+        let is_spotted = tank_transform
+            .translation
+            .distance(mine.transform.translation)
+            <= mine_detection_range;
+
+        if is_friendly || is_spotted {
+            client_state.mines.insert(*entity, mine.clone());
+        }
+    });
+
     // Updating the tick
     client_state.tick = lobby_state.tick;
 }
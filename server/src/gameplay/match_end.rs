@@ -0,0 +1,73 @@
+use bevy::{prelude::*, utils::hashbrown::HashSet};
+use shared::{
+    game::player_handling::PlayerState,
+    networking::{
+        lobby_management::{
+            lobby_management::LobbyManagementSystemParam, InTeam, LobbyRosterChangedTrigger,
+            LobbyState,
+        },
+        messages::{
+            message_container::NetworkMessageType, message_data::game_finished::GameFinished,
+            message_queue::ImmediateOutMessageQueue,
+        },
+    },
+};
+
+use crate::networking::lobby_management::lobby_membership::{get_members, notify_members};
+
+use super::triggers::AddStateUpdateToQueue;
+
+/// Finishes the match once at most one team has living tanks left (none, on a map with a single team)
+/// Runs after the state of the tick was queued, so the clients still receive the final state
+pub fn check_if_match_finished(
+    trigger: Trigger<AddStateUpdateToQueue>,
+    mut lobby_management: LobbyManagementSystemParam,
+    tanks: Query<(&PlayerState, &InTeam)>,
+    mut message_queues: Query<&mut ImmediateOutMessageQueue>,
+    mut commands: Commands,
+) {
+    let lobby_entity = trigger.entity();
+    let lobby = lobby_management
+        .get_lobby(lobby_entity)
+        .expect("Failed to get lobby");
+    if lobby.state != LobbyState::InProgress {
+        return;
+    }
+
+    let team_count = lobby
+        .map_config
+        .as_ref()
+        .expect("Map config should be set up by now")
+        .teams
+        .len();
+    // Tanks of players awaiting their reconnect are still in the game
+    let living_teams = lobby
+        .players
+        .iter()
+        .filter_map(|(_, player, _)| tanks.get(*player).ok())
+        .filter(|(player_state, _)| **player_state != PlayerState::Dead)
+        .map(|(_, team)| (**team).clone())
+        .collect::<HashSet<_>>();
+    if living_teams.len() >= team_count.min(2) {
+        return;
+    }
+
+    let winning_team = living_teams.into_iter().next();
+    info!(
+        "Game in lobby \"{}\" finished, winning team: {:?}",
+        lobby.lobby_name, winning_team
+    );
+
+    let members = get_members(&lobby_management, lobby_entity);
+    lobby_management
+        .get_lobby_mut(lobby_entity)
+        .expect("Failed to get lobby")
+        .state = LobbyState::Finished;
+
+    notify_members(
+        &mut message_queues,
+        members,
+        NetworkMessageType::GameFinished(GameFinished { winning_team }),
+    );
+    commands.trigger_targets(LobbyRosterChangedTrigger, lobby_entity);
+}
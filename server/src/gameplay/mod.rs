@@ -2,12 +2,16 @@ use bevy::prelude::*;
 use handle_collisions::MyCollisionHandlingPlugin;
 use handle_players::HandlePlayersPlugin;
 use shared::networking::lobby_management::MyLobby;
+
+use crate::networking::handle_clients::lib::MyNetworkClient;
 use system_sets::MyGameplaySet;
 use tick_systems::TickSystemsPlugin;
 
+pub mod delta_game_states;
 pub mod game_state_handling;
 pub mod handle_collisions;
 pub mod handle_players;
+pub mod match_end;
 pub mod process_messages;
 pub mod process_messages_when_lobby_not_ready;
 pub mod simulation;
@@ -52,14 +56,23 @@ impl Plugin for MyGameplayPlugin {
                     .in_set(MyGameplaySet::ProcessMessagesBeforeLobbyReady),
             ),
         )
-        .add_observer(add_observers_to_lobby);
+        .add_observer(add_observers_to_lobby)
+        .add_observer(add_observers_to_client);
     }
 }
 
+fn add_observers_to_client(trigger: Trigger<OnAdd, MyNetworkClient>, mut commands: Commands) {
+    commands
+        .entity(trigger.entity())
+        .observe(delta_game_states::handle_acknowledge_game_state)
+        .observe(delta_game_states::handle_request_full_game_state);
+}
+
 fn add_observers_to_lobby(trigger: Trigger<OnAdd, MyLobby>, mut commands: Commands) {
     commands
         .entity(trigger.entity())
         .observe(game_state_handling::add_current_game_state_to_message_queue)
+        .observe(match_end::check_if_match_finished)
         .observe(game_state_handling::update_lobby_state)
         .observe(simulation::process_tick_sim)
         .observe(simulation::process_tick_sim_finished)
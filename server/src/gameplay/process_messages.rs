@@ -6,11 +6,18 @@ use shared::networking::{
     },
     messages::{
         message_container::{MessageContainer, MessageTarget, NetworkMessageType},
+        message_data::{
+            command_acknowledgement::{AcknowledgedCommand, CommandRejectionReason},
+            message_error_types::ErrorMessageTypes,
+        },
         message_queue::{ImmediateOutMessageQueue, OutMessageQueue},
     },
 };
 
-use crate::gameplay::triggers::StartNextSimulationStepTrigger;
+use crate::gameplay::{
+    handle_players::command_acknowledgements::CommandProcessedTrigger,
+    triggers::StartNextSimulationStepTrigger,
+};
 
 use super::triggers::CollectAndTriggerMessagesTrigger;
 
@@ -38,9 +45,14 @@ pub fn process_lobby_messages(
     for message_container in messages_to_process {
         if message_container.tick_to_be_processed_at > current_lobby_state.tick {
             warn!(
-                "Dropping message because it's too old: {:?}",
+                "Dropping message because it's scheduled for a later tick: {:?}",
                 message_container
             );
+            reject_command(
+                &mut commands,
+                &message_container,
+                CommandRejectionReason::ScheduledForLaterTick,
+            );
             continue;
         }
 
@@ -68,6 +80,14 @@ pub fn process_lobby_messages(
                 e, message_container
             );
 
+            if let ErrorMessageTypes::InvalidTarget(_) = e {
+                reject_command(
+                    &mut commands,
+                    &message_container,
+                    CommandRejectionReason::InvalidTarget,
+                );
+            }
+
             let mut error_queue = immediate_message_queues
                 .get_mut(sender)
                 // TODO Replace with adding error to queue, not panicking
@@ -81,3 +101,24 @@ pub fn process_lobby_messages(
 
     commands.trigger_targets(StartNextSimulationStepTrigger, lobby_entity);
 }
+
+/// Lets the sender know that its command was not applied, if the message is a command
+fn reject_command(
+    commands: &mut Commands,
+    message_container: &MessageContainer,
+    rejection_reason: CommandRejectionReason,
+) {
+    if let (Some(sender), Some(command)) = (
+        message_container.sender,
+        AcknowledgedCommand::from_message(&message_container.message),
+    ) {
+        commands.trigger_targets(
+            CommandProcessedTrigger::rejected(
+                command,
+                message_container.tick_sent,
+                rejection_reason,
+            ),
+            sender,
+        );
+    }
+}
@@ -51,7 +51,14 @@ pub fn process_messages_before_lobby_is_ready(
 
         for message_container in messages_to_process {
             match message_container.message {
-                NetworkMessageType::StartGame(_) => {
+                NetworkMessageType::StartGame(_)
+                | NetworkMessageType::LeaveLobby(_)
+                | NetworkMessageType::SwitchTeam(_)
+                | NetworkMessageType::SetReady(_)
+                | NetworkMessageType::KickMember(_)
+                | NetworkMessageType::TransferHost(_)
+                | NetworkMessageType::ChangeLobbySettings(_)
+                | NetworkMessageType::Rematch(_) => {
                     // received messages that are allowed before lobby is ready
                 }
                 _ => {
@@ -1,6 +1,8 @@
 use bevy::{prelude::*, utils::HashMap};
 use shared::{
-    asset_handling::config::{ServerConfigSystemParam, TankConfigSystemParam},
+    asset_handling::config::{
+        ServerConfig, ServerConfigSystemParam, TankConfigSystemParam, TankConfigs,
+    },
     game::{player_handling::TankBodyMarker, tank_types::TankType},
     networking::{
         lobby_management::{
@@ -21,7 +23,9 @@ use shared::{
     },
 };
 
-use crate::networking::handle_clients::lib::MyNetworkClient;
+use crate::networking::{
+    handle_clients::lib::MyNetworkClient, lib::MyNetworkingThread, transport::dummy::DummyTransport,
+};
 
 use super::handle_players::{
     dummy_handling::DummyClientMarker, handle_spawning::RespawnPlayerTrigger,
@@ -35,6 +39,7 @@ pub fn check_if_lobby_should_start(
     mut lobbies: Query<&mut MyLobby>,
     mut client_queues: Query<&mut ImmediateOutMessageQueue>,
     mut clients: Query<&mut MyNetworkClient>,
+    networking_thread: Res<MyNetworkingThread>,
     mut commands: Commands,
 ) {
     let lobby_entity = trigger.entity();
@@ -57,6 +62,35 @@ pub fn check_if_lobby_should_start(
         return;
     }
 
+    if start_config.force && !lobby.is_host(sender) {
+        sender_queue.push_back(MessageContainer::new(
+            MessageTarget::Client(sender),
+            NetworkMessageType::MessageError(ErrorMessageTypes::NotLobbyHost(format!(
+                "Only the host of lobby \"{}\" can force a start",
+                lobby.lobby_name
+            ))),
+        ));
+
+        return;
+    }
+
+    if !start_config.force && !lobby.all_players_ready() {
+        sender_queue.push_back(MessageContainer::new(
+            MessageTarget::Client(sender),
+            NetworkMessageType::MessageError(ErrorMessageTypes::LobbyNotReadyToStart(format!(
+                "Not all players are ready: {} of {}",
+                lobby.ready_players.len(),
+                lobby
+                    .players
+                    .iter()
+                    .filter(|(_, _, client_type)| *client_type != ClientType::Dummy)
+                    .count()
+            ))),
+        ));
+
+        return;
+    }
+
     // Assign every player, that hasn't already, a spawnpoint.
     let map_config = lobby.map_config.as_mut().expect("Failed to get map config");
     // Team name -> spawn point id
@@ -120,7 +154,10 @@ pub fn check_if_lobby_should_start(
 
             for i in 0..needed_players {
                 let dummy_name = format!("{}-dummy-{}", team_name, i);
-                let mut dummy_client = MyNetworkClient::new_dummy(dummy_name.clone());
+                let mut dummy_client = MyNetworkClient::new_dummy(
+                    dummy_name.clone(),
+                    networking_thread.connect(DummyTransport),
+                );
 
                 // Find the first spawn point that is not taken for this team,
                 // or just take the first one if the only available spawn point is already assigned
@@ -193,16 +230,6 @@ pub fn start_lobby(
     let lobby = lobby_management
         .get_lobby(lobby_entity)
         .expect("Failed to get lobby");
-    let map = &lobby
-        .map_config
-        .as_ref()
-        .expect("Failed to get map config")
-        .map;
-    let team_configs = &lobby
-        .map_config
-        .as_ref()
-        .expect("Failed to get map config")
-        .teams;
 
     let server_config = server_config.server_config();
     let tank_configs = tank_config.tank_configs();
@@ -228,14 +255,13 @@ pub fn start_lobby(
 
                 queue.push_back(MessageContainer::new(
                     MessageTarget::Client(client_entity),
-                    NetworkMessageType::GameStarts(GameStarts {
-                        client_id: client_entity,
-                        connected_clients: connected_clients.clone(),
-                        tick_rate: server_config.tick_rate,
-                        map_definition: map.clone(),
-                        team_configs: team_configs.clone(),
-                        tank_configs: tank_configs.tanks.clone(),
-                    }),
+                    NetworkMessageType::GameStarts(game_starts_for_client(
+                        client_entity,
+                        lobby,
+                        connected_clients.clone(),
+                        server_config,
+                        tank_configs,
+                    )),
                 ));
             }
 
@@ -248,7 +274,27 @@ pub fn start_lobby(
     }
 }
 
-fn get_connected_configs_in_lobby(
+/// The game configuration sent to a client when the game starts, or when it reconnects to the running game
+pub fn game_starts_for_client(
+    client_entity: Entity,
+    lobby: &MyLobby,
+    connected_clients: Vec<ConnectedClientConfig>,
+    server_config: &ServerConfig,
+    tank_configs: &TankConfigs,
+) -> GameStarts {
+    let map_config = lobby.map_config.as_ref().expect("Failed to get map config");
+
+    GameStarts {
+        client_id: client_entity,
+        connected_clients,
+        tick_rate: server_config.tick_rate,
+        map_definition: map_config.map.clone(),
+        team_configs: map_config.teams.clone(),
+        tank_configs: tank_configs.tanks.clone(),
+    }
+}
+
+pub fn get_connected_configs_in_lobby(
     lobby_management: &LobbyManagementSystemParam,
     lobby_entity: Entity,
     clients: &Query<(&MyNetworkClient, &ClientType, Option<&TankType>)>,
@@ -6,48 +6,28 @@ use shared::{
 
 use crate::networking::{
     handle_clients::lib::{ClientConnectedTrigger, MyNetworkClient},
-    lib::MyTcpListener,
+    lib::MyNetworkingThread,
 };
 
 /// System that checks the channel for newly accepted connections,
 pub fn accept_connections_system(
     mut commands: Commands,
-    my_listener: Res<MyTcpListener>,
+    networking_thread: Res<MyNetworkingThread>,
     server_config: ServerConfigSystemParam,
 ) {
     let config = server_config.server_config();
-    // Accept in a loop until we get a WouldBlock error
-    loop {
-        match my_listener.listener.accept() {
-            Ok((stream, _addr)) => {
-                stream.set_nonblocking(true).unwrap();
 
-                let networked_client = commands
-                    .spawn((
-                        MyNetworkClient::new(stream),
-                        AwaitingFirstContact::new(config.timeout_first_contact),
-                    ))
-                    .observe(remove_player_from_lobby)
-                    .id();
+    for connection in networking_thread.new_connections.try_iter() {
+        let networked_client = commands
+            .spawn((
+                MyNetworkClient::new(connection),
+                AwaitingFirstContact::new(config.timeout_first_contact),
+            ))
+            .observe(remove_player_from_lobby)
+            .id();
 
-                info!("New client connected: {:?}", networked_client);
+        info!("New client connected: {:?}", networked_client);
 
-                commands.trigger(ClientConnectedTrigger(networked_client));
-            }
-            Err(e) => {
-                use std::io::ErrorKind;
-                match e.kind() {
-                    ErrorKind::WouldBlock => {
-                        // No more incoming connections right now
-                        break;
-                    }
-                    _ => {
-                        // Some other error, e.g. connection reset, etc.
-                        eprintln!("Accept error: {}", e);
-                        break;
-                    }
-                }
-            }
-        }
+        commands.trigger(ClientConnectedTrigger(networked_client));
     }
 }
@@ -1,16 +1,58 @@
 use bevy::prelude::*;
-use shared::networking::lobby_management::{lobby_management::LobbyManagementSystemParam, InLobby};
+use shared::{
+    asset_handling::config::ServerConfigSystemParam,
+    game::player_handling::TankMomentum,
+    networking::{
+        lobby_management::{lobby_management::LobbyManagementSystemParam, InLobby, LobbyState},
+        messages::message_data::{first_contact::ClientType, lobby_membership::LobbyRemovalReason},
+    },
+};
+
+use crate::{
+    gameplay::delta_game_states::SentGameStates,
+    networking::{
+        lib::MyNetworkingThread,
+        lobby_management::reconnect::{AwaitingReconnect, ClientSessionToken},
+        transport::dummy::DummyTransport,
+    },
+};
 
 use super::lib::{ClientDisconnectedTrigger, MyNetworkClient};
 
+/// The disconnecting clients, with everything needed to decide whether they can reconnect
+type DisconnectingClients<'a> = (
+    Entity,
+    &'a mut MyNetworkClient,
+    &'a mut SentGameStates,
+    Option<&'a InLobby>,
+    Option<&'a ClientType>,
+    Option<&'a AwaitingReconnect>,
+    Option<&'a mut TankMomentum>,
+);
+
 pub fn handle_client_disconnects(
     disconnected_client: Trigger<ClientDisconnectedTrigger>,
-    clients: Query<(Entity, &MyNetworkClient, Option<&InLobby>)>,
+    mut clients: Query<DisconnectingClients>,
+    session_tokens: Query<&ClientSessionToken>,
+    networking_thread: Res<MyNetworkingThread>,
+    server_config: ServerConfigSystemParam,
     mut commands: Commands,
     mut lobby_management: LobbyManagementSystemParam,
 ) {
     let disconnected_client = **disconnected_client;
-    let (networked_entity, networked_client, in_lobby) = clients.get(disconnected_client).unwrap();
+    let (
+        networked_entity,
+        mut networked_client,
+        mut sent_game_states,
+        in_lobby,
+        client_type,
+        awaiting_reconnect,
+        momentum,
+    ) = match clients.get_mut(disconnected_client) {
+        Ok(client) => client,
+        // Already handled, e.g. when reading and sending failed in the same update
+        Err(_) => return,
+    };
 
     info!(
         "Client disconnected: {:?} ({:?})",
@@ -18,6 +60,36 @@ pub fn handle_client_disconnects(
         networked_client.get_address(),
     );
 
+    // Players of a running match keep their tank for a while, so they can reconnect with their session token
+    if let Some(in_lobby) = in_lobby {
+        let match_in_progress = lobby_management
+            .get_lobby(**in_lobby)
+            .is_ok_and(|lobby| lobby.state == LobbyState::InProgress);
+        if match_in_progress
+            && awaiting_reconnect.is_none()
+            && client_type == Some(&ClientType::Player)
+            && session_tokens.contains(networked_entity)
+        {
+            let grace_period = server_config.server_config().reconnect_grace_period;
+            info!(
+                "Client was in a running match, waiting {}ms for it to reconnect...",
+                grace_period
+            );
+
+            // The tank idles, as nothing is received from the dummy transport and it stops driving
+            networked_client.connection = networking_thread.connect(DummyTransport);
+            if let Some(mut momentum) = momentum {
+                momentum.stop();
+            }
+            sent_game_states.reset();
+            commands
+                .entity(networked_entity)
+                .insert(AwaitingReconnect::new(grace_period));
+
+            return;
+        }
+    }
+
     commands.entity(networked_entity).despawn_recursive();
 
     if let Some(in_lobby) = in_lobby {
@@ -25,6 +97,11 @@ pub fn handle_client_disconnects(
             "Client was in lobby: {:?}, removing from lobby...",
             in_lobby
         );
-        lobby_management.remove_player_from_lobby(networked_entity, **in_lobby, &mut commands);
+        lobby_management.remove_player_from_lobby(
+            networked_entity,
+            **in_lobby,
+            LobbyRemovalReason::Disconnected,
+            &mut commands,
+        );
     }
 }
@@ -1,40 +1,54 @@
-use std::net::TcpStream;
-
 use bevy::prelude::*;
 use shared::{
     game::game_state::PersonalizedClientGameState,
-    networking::messages::message_queue::{ImmediateOutMessageQueue, OutMessageQueue},
+    networking::{
+        message_codec::MessageEncoding,
+        messages::message_queue::{ImmediateOutMessageQueue, OutMessageQueue},
+    },
+};
+
+use crate::{
+    gameplay::delta_game_states::SentGameStates,
+    networking::{
+        handle_messages::rate_limit::MessageRateLimit, networking_thread::ClientConnection,
+    },
 };
 
 #[derive(Debug, Component)]
-#[require(OutMessageQueue, ImmediateOutMessageQueue, PersonalizedClientGameState)]
+#[require(
+    OutMessageQueue,
+    ImmediateOutMessageQueue,
+    PersonalizedClientGameState,
+    MessageEncoding,
+    SentGameStates,
+    MessageRateLimit
+)]
 pub struct MyNetworkClient {
     pub name: Option<String>,
     pub assigned_spawn_point: Option<usize>,
-    pub stream: Option<TcpStream>,
+    pub connection: ClientConnection,
 }
 
 impl MyNetworkClient {
-    pub fn new(stream: TcpStream) -> Self {
+    pub fn new(connection: ClientConnection) -> Self {
         Self {
             name: None,
-            stream: Some(stream),
+            connection,
             assigned_spawn_point: None,
         }
     }
 
-    pub fn new_dummy(name: String) -> Self {
+    /// Dummies are connected using a `DummyTransport`
+    pub fn new_dummy(name: String, connection: ClientConnection) -> Self {
         Self {
             name: Some(name),
-            stream: None,
+            connection,
             assigned_spawn_point: None,
         }
     }
 
     pub fn get_address(&self) -> Option<String> {
-        self.stream
-            .as_ref()
-            .map(|s| s.peer_addr().unwrap().to_string())
+        self.connection.address()
     }
 }
 
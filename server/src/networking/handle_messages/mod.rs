@@ -3,6 +3,7 @@ use receiving_messages::handle_reading_messages;
 use sending_messages::{sending_client_messages, sending_immediate_messages};
 use shared::networking::{lobby_management::MyLobby, networking_system_sets::MyNetworkingSet};
 
+pub mod rate_limit;
 pub mod receiving_messages;
 pub mod sending_messages;
 
@@ -0,0 +1,175 @@
+use std::mem::{discriminant, Discriminant};
+
+use bevy::{prelude::*, utils::HashMap};
+use shared::{
+    asset_handling::config::ServerConfig,
+    networking::messages::{
+        message_container::NetworkMessageType,
+        message_data::{
+            command_acknowledgement::AcknowledgedCommand, message_error_types::ErrorMessageTypes,
+        },
+    },
+};
+
+/// Counts what a client sent during the current tick, to enforce the limits of the server config
+#[derive(Debug, Component, Default)]
+pub struct MessageRateLimit {
+    /// The tick the counts belong to, None if the client is not in a running match
+    tick: Option<u64>,
+    messages: usize,
+    commands: HashMap<Discriminant<NetworkMessageType>, usize>,
+    /// Whether the client already broke a limit during the current tick
+    violated_this_tick: bool,
+    /// How often the client broke a limit, at most once per tick
+    pub violations: u32,
+}
+
+impl MessageRateLimit {
+    /// Resets the counts if the tick changed
+    /// Outside of a running match there are no ticks, so the counts are reset on every call
+    pub fn start_tick(&mut self, tick: Option<u64>) {
+        if tick.is_none() || tick != self.tick {
+            self.tick = tick;
+            self.messages = 0;
+            self.commands.clear();
+            self.violated_this_tick = false;
+        }
+    }
+
+    /// Counts the message, returns an error if it exceeds one of the limits and has to be dropped
+    pub fn check(
+        &mut self,
+        message: &NetworkMessageType,
+        config: &ServerConfig,
+    ) -> Result<(), ErrorMessageTypes> {
+        self.messages += 1;
+        if self.messages > config.max_messages_per_tick {
+            return Err(ErrorMessageTypes::RateLimitExceeded(format!(
+                "More than {} messages per tick",
+                config.max_messages_per_tick
+            )));
+        }
+
+        if AcknowledgedCommand::from_message(message).is_some() {
+            let count = self.commands.entry(discriminant(message)).or_default();
+            *count += 1;
+            if *count > config.max_commands_per_tick {
+                return Err(ErrorMessageTypes::RateLimitExceeded(format!(
+                    "More than {} commands of the same type per tick",
+                    config.max_commands_per_tick
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Registers that the client broke a limit
+    /// Returns whether this is the first violation of the tick, only those are counted and answered
+    pub fn register_violation(&mut self) -> bool {
+        if self.violated_this_tick {
+            return false;
+        }
+        self.violated_this_tick = true;
+        self.violations += 1;
+
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use shared::networking::messages::message_data::{
+        game_state::AcknowledgeGameState, tank_messages::move_tank::MoveTankCommand,
+    };
+
+    use super::*;
+
+    fn config() -> ServerConfig {
+        ServerConfig {
+            max_messages_per_tick: 3,
+            max_commands_per_tick: 1,
+            max_violations: 2,
+            ..default()
+        }
+    }
+
+    fn message() -> NetworkMessageType {
+        NetworkMessageType::AcknowledgeGameState(AcknowledgeGameState { tick: 0 })
+    }
+
+    fn command() -> NetworkMessageType {
+        NetworkMessageType::MoveTankCommand(MoveTankCommand { distance: 1.0 })
+    }
+
+    #[test]
+    fn messages_beyond_the_limit_are_rejected() {
+        let config = config();
+        let mut rate_limit = MessageRateLimit::default();
+        rate_limit.start_tick(Some(1));
+
+        for _ in 0..3 {
+            assert!(rate_limit.check(&message(), &config).is_ok());
+        }
+        assert!(rate_limit.check(&message(), &config).is_err());
+    }
+
+    #[test]
+    fn commands_beyond_the_limit_are_rejected() {
+        let config = config();
+        let mut rate_limit = MessageRateLimit::default();
+        rate_limit.start_tick(Some(1));
+
+        assert!(rate_limit.check(&command(), &config).is_ok());
+        assert!(rate_limit.check(&command(), &config).is_err());
+        assert!(rate_limit.check(&message(), &config).is_ok());
+    }
+
+    #[test]
+    fn violations_are_counted_once_per_tick() {
+        let mut rate_limit = MessageRateLimit::default();
+        rate_limit.start_tick(Some(1));
+
+        assert!(rate_limit.register_violation());
+        assert!(!rate_limit.register_violation());
+        assert_eq!(rate_limit.violations, 1);
+
+        // The same tick does not reset anything
+        rate_limit.start_tick(Some(1));
+        assert!(!rate_limit.register_violation());
+
+        rate_limit.start_tick(Some(2));
+        assert!(rate_limit.register_violation());
+        assert_eq!(rate_limit.violations, 2);
+    }
+
+    #[test]
+    fn counts_are_reset_on_a_new_tick() {
+        let config = config();
+        let mut rate_limit = MessageRateLimit::default();
+        rate_limit.start_tick(Some(1));
+
+        assert!(rate_limit.check(&command(), &config).is_ok());
+        assert!(rate_limit.check(&command(), &config).is_err());
+
+        rate_limit.start_tick(Some(1));
+        assert!(rate_limit.check(&command(), &config).is_err());
+
+        rate_limit.start_tick(Some(2));
+        assert!(rate_limit.check(&command(), &config).is_ok());
+    }
+
+    #[test]
+    fn counts_are_reset_on_every_call_outside_of_a_running_match() {
+        let config = config();
+        let mut rate_limit = MessageRateLimit::default();
+
+        for _ in 0..5 {
+            rate_limit.start_tick(None);
+            assert!(rate_limit.check(&command(), &config).is_ok());
+            assert!(rate_limit.check(&command(), &config).is_err());
+            assert!(rate_limit.register_violation());
+        }
+        assert_eq!(rate_limit.violations, 5);
+    }
+}
@@ -1,141 +1,202 @@
-use std::io::Read;
-
 use bevy::prelude::*;
-use shared::networking::{
-    lobby_management::{
-        lobby_management::{LobbyManagementArgument, LobbyManagementSystemParam},
-        InLobby, InTeam,
-    },
-    messages::{
-        message_container::{MessageContainer, MessageTarget, NetworkMessageType},
-        message_queue::{ImmediateOutMessageQueue, OutMessageQueue},
+use shared::{
+    asset_handling::config::{ServerConfig, ServerConfigSystemParam},
+    networking::{
+        lobby_management::{
+            lobby_management::{LobbyManagementArgument, LobbyManagementSystemParam},
+            InLobby, InTeam, LobbyState,
+        },
+        messages::{
+            message_container::{MessageContainer, MessageTarget, NetworkMessageType},
+            message_data::message_error_types::ErrorMessageTypes,
+            message_queue::{ImmediateOutMessageQueue, OutMessageQueue},
+        },
     },
 };
 
-use crate::networking::handle_clients::lib::{ClientDisconnectedTrigger, MyNetworkClient};
+use crate::networking::{
+    handle_clients::lib::{ClientDisconnectedTrigger, MyNetworkClient},
+    networking_thread::IncomingBatch,
+};
+
+use super::rate_limit::MessageRateLimit;
+
+/// The clients to read messages from, with their limits and where their messages go
+type ReadingClients<'a> = (
+    Entity,
+    &'a MyNetworkClient,
+    &'a mut MessageRateLimit,
+    Option<&'a InLobby>,
+    Option<&'a InTeam>,
+);
 
 pub fn handle_reading_messages(
     mut commands: Commands,
-    mut clients: Query<(
-        Entity,
-        &mut MyNetworkClient,
-        Option<&InLobby>,
-        Option<&InTeam>,
-    )>,
+    mut clients: Query<ReadingClients>,
     mut outgoing_message_queues: Query<&mut OutMessageQueue>,
     mut immediate_message_queues: Query<&mut ImmediateOutMessageQueue>,
     mut lobby_management: LobbyManagementSystemParam,
+    server_config: ServerConfigSystemParam,
 ) {
-    for (sender, mut network_client, in_lobby, in_team) in clients.iter_mut() {
+    let config = server_config.server_config();
+
+    'clients: for (sender, network_client, mut rate_limit, in_lobby, in_team) in clients.iter_mut()
+    {
         let addr = network_client.get_address();
-        if let Some(stream) = &mut network_client.stream {
-            // Read the 4-byte length prefix for the payload length
-            let mut len_buf = [0u8; 4];
-            if let Err(e) = stream.read_exact(&mut len_buf) {
-                if e.kind() == std::io::ErrorKind::WouldBlock {
-                    continue;
-                } else {
-                    error!("Error reading length prefix from {:?}: {}", addr, e);
-                    commands.trigger(ClientDisconnectedTrigger(sender));
-                    continue;
-                }
-            }
-            let msg_len = u32::from_be_bytes(len_buf) as usize;
-            if msg_len == 0 {
-                // No message to read
-                continue;
-            }
 
-            // Read the actual message payload into a buffer
-            let mut buf = vec![0u8; msg_len];
-            if let Err(e) = stream.read_exact(&mut buf) {
-                error!(
-                    "Read error: failed to fill whole buffer from {:?}: {}",
-                    addr, e
-                );
-                commands.trigger(ClientDisconnectedTrigger(sender));
-                continue;
-            }
+        // Ticks only advance while the match is running, before and after it the counts are per update
+        rate_limit.start_tick(
+            in_lobby
+                .filter(|in_lobby| {
+                    lobby_management
+                        .get_lobby(***in_lobby)
+                        .is_ok_and(|lobby| lobby.state == LobbyState::InProgress)
+                })
+                .map(|in_lobby| {
+                    lobby_management
+                        .get_lobby_gamestate(**in_lobby)
+                        // TODO Replace with adding error to queue, not panicking
+                        .expect("Failed to get lobby game state")
+                        .tick
+                }),
+        );
 
-            // Convert buffer to UTF-8 string
-            let received = match String::from_utf8(buf) {
-                Ok(s) => s,
-                Err(e) => {
-                    error!("UTF-8 conversion error from {:?}: {}", addr, e);
+        // Handle all batches of messages the networking thread received since the last update
+        loop {
+            let mut messages = match network_client.connection.try_receive() {
+                Ok(Some(IncomingBatch::Messages(messages))) => messages,
+                Ok(Some(IncomingBatch::FrameTooLarge(e))) => {
+                    warn!("Discarded frame from {:?}: {}", addr, e);
+                    if handle_violation(
+                        &mut commands,
+                        &mut immediate_message_queues,
+                        &mut rate_limit,
+                        config,
+                        sender,
+                        ErrorMessageTypes::FrameTooLarge(e),
+                    ) {
+                        continue 'clients;
+                    }
                     continue;
                 }
+                Ok(None) => break,
+                Err(e) => {
+                    error!("Error reading from {:?}: {}", addr, e);
+                    commands.trigger(ClientDisconnectedTrigger(sender));
+                    break;
+                }
             };
 
-            // Deserialize the JSON into an array of MessageContainers
-            match serde_json::from_str::<Vec<MessageContainer>>(&received) {
-                Ok(mut messages) => {
-                    for message_container in messages.iter_mut() {
-                        message_container.sender = Some(sender);
-                        // If we're in the lobby, add all messages to the lobby's message queue, so we can process them in the correct moment. expecting all non-server-only messages
-                        if let Some(in_lobby) = in_lobby {
-                            // Set the received tick to the current tick of the lobby
-                            message_container.tick_received = lobby_management
-                                .get_lobby_gamestate(**in_lobby)
-                                // TODO Replace with adding error to queue, not panicking
-                                .expect("Failed to get lobby game state")
-                                .tick;
-                            message_container.tick_to_be_processed_at =
-                                message_container.tick_received + 1;
-
-                            // Add message to the lobby's message queue
-                            lobby_management
-                                .get_lobby_mut(**in_lobby)
-                                // TODO Replace with adding error to queue, not panicking
-                                .expect("Failed to get lobby")
-                                .messages
-                                .push_back(message_container.clone());
-                        } else {
-                            // If we're not in the lobby, add the message to the immediate message queue. expecting server only messages
-                            let lobby_arg = LobbyManagementArgument {
-                                lobby: in_lobby.map(|l| **l),
-                                sender: Some(sender),
-                                target_player: match message_container.target {
-                                    MessageTarget::Client(e) => Some(e),
-                                    _ => None,
-                                },
-                                team_name: in_team.map(|t| t.0.clone()),
-                            };
-
-                            let result = message_container.trigger_message_received(
-                                &mut commands,
-                                &lobby_management,
-                                lobby_arg,
-                                &mut outgoing_message_queues,
-                            );
-
-                            if let Err(e) = result {
-                                error!(
-                                    "Failed to handle message from client \"{:?}\":\n{:?}",
-                                    addr, e
-                                );
-
-                                let mut error_queue = immediate_message_queues
-                                    .get_mut(sender)
-                                    // TODO Replace with adding error to queue, not panicking
-                                    .expect("Failed to get outgoing message queue from sender");
-                                error_queue.push_back(MessageContainer::new(
-                                    MessageTarget::Client(sender),
-                                    NetworkMessageType::MessageError(e),
-                                ));
-                            }
-                        }
+            for message_container in messages.iter_mut() {
+                message_container.sender = Some(sender);
+
+                if let Err(e) = rate_limit.check(&message_container.message, config) {
+                    if handle_violation(
+                        &mut commands,
+                        &mut immediate_message_queues,
+                        &mut rate_limit,
+                        config,
+                        sender,
+                        e,
+                    ) {
+                        continue 'clients;
                     }
+                    continue;
                 }
-                Err(e) => {
-                    error!(
-                        "Failed to parse JSON array from {:?}: {}. Raw data: {}",
-                        addr, e, received
+
+                // If we're in the lobby, add all messages to the lobby's message queue, so we can process them in the correct moment. expecting all non-server-only messages
+                if let Some(in_lobby) = in_lobby {
+                    // Set the received tick to the current tick of the lobby
+                    message_container.tick_received = lobby_management
+                        .get_lobby_gamestate(**in_lobby)
+                        // TODO Replace with adding error to queue, not panicking
+                        .expect("Failed to get lobby game state")
+                        .tick;
+                    message_container.tick_to_be_processed_at = message_container.tick_received + 1;
+
+                    // Add message to the lobby's message queue
+                    lobby_management
+                        .get_lobby_mut(**in_lobby)
+                        // TODO Replace with adding error to queue, not panicking
+                        .expect("Failed to get lobby")
+                        .messages
+                        .push_back(message_container.clone());
+                } else {
+                    // If we're not in the lobby, add the message to the immediate message queue. expecting server only messages
+                    let lobby_arg = LobbyManagementArgument {
+                        lobby: in_lobby.map(|l| **l),
+                        sender: Some(sender),
+                        target_player: match message_container.target {
+                            MessageTarget::Client(e) => Some(e),
+                            _ => None,
+                        },
+                        team_name: in_team.map(|t| t.0.clone()),
+                    };
+
+                    let result = message_container.trigger_message_received(
+                        &mut commands,
+                        &lobby_management,
+                        lobby_arg,
+                        &mut outgoing_message_queues,
                     );
-                    // TODO add error message to queue
+
+                    if let Err(e) = result {
+                        error!(
+                            "Failed to handle message from client \"{:?}\":\n{:?}",
+                            addr, e
+                        );
+
+                        let mut error_queue = immediate_message_queues
+                            .get_mut(sender)
+                            // TODO Replace with adding error to queue, not panicking
+                            .expect("Failed to get outgoing message queue from sender");
+                        error_queue.push_back(MessageContainer::new(
+                            MessageTarget::Client(sender),
+                            NetworkMessageType::MessageError(e),
+                        ));
+                    }
                 }
             }
-        } else {
-            // No Stream, it's a dummy client
+
+            // Messages sent outside of a lobby (e.g. the first contact) may add the client to a lobby,
+            // which only takes effect after this system. The remaining batches are handled in the next update.
+            if in_lobby.is_none() {
+                break;
+            }
         }
     }
 }
+
+/// Answers the first violation of a tick with an error and disconnects clients that broke the limits too often
+/// Returns whether the client was disconnected
+fn handle_violation(
+    commands: &mut Commands,
+    immediate_message_queues: &mut Query<&mut ImmediateOutMessageQueue>,
+    rate_limit: &mut MessageRateLimit,
+    config: &ServerConfig,
+    sender: Entity,
+    error: ErrorMessageTypes,
+) -> bool {
+    if !rate_limit.register_violation() {
+        return false;
+    }
+
+    if rate_limit.violations > config.max_violations {
+        warn!(
+            "Disconnecting client {:?} after {} violations of the limits",
+            sender, rate_limit.violations
+        );
+        commands.trigger(ClientDisconnectedTrigger(sender));
+        return true;
+    }
+
+    let mut error_queue = immediate_message_queues
+        .get_mut(sender)
+        .expect("Failed to get outgoing message queue from sender");
+    error_queue.push_back(MessageContainer::new(
+        MessageTarget::Client(sender),
+        NetworkMessageType::MessageError(error),
+    ));
+
+    false
+}
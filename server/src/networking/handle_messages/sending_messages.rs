@@ -1,36 +1,39 @@
-use std::io::Write;
-
 use bevy::prelude::*;
 use shared::networking::{
     lobby_management::lobby_management::{LobbyManagementArgument, LobbyManagementSystemParam},
+    message_codec::MessageEncoding,
     messages::message_queue::{ImmediateOutMessageQueue, OutMessageQueue},
 };
 
 use crate::{
     gameplay::triggers::SendOutgoingMessagesTrigger,
-    networking::handle_clients::lib::MyNetworkClient,
+    networking::handle_clients::lib::{ClientDisconnectedTrigger, MyNetworkClient},
 };
 
 pub fn sending_immediate_messages(
+    mut commands: Commands,
     mut connected_clients: Query<
-        (&mut MyNetworkClient, &mut ImmediateOutMessageQueue),
+        (
+            Entity,
+            &MyNetworkClient,
+            &MessageEncoding,
+            &mut ImmediateOutMessageQueue,
+        ),
         Changed<ImmediateOutMessageQueue>,
     >,
 ) {
-    for (mut client, mut immediate_message_queue) in connected_clients.iter_mut() {
+    for (entity, client, encoding, mut immediate_message_queue) in connected_clients.iter_mut() {
         let messages: Vec<_> = immediate_message_queue.drain(..).collect();
-        if let Some(stream) = &mut client.stream {
-            if !messages.is_empty() {
-                let messages = serde_json::to_vec(&messages).expect("Failed to serialize messages");
-                let length = (messages.len() as u32).to_le_bytes();
-
-                let _ = stream.write_all(&length).expect("Failed to send length");
-                let _ = stream
-                    .write_all(&messages)
-                    .expect("Failed to send messages");
+        if !messages.is_empty() {
+            // Encoding and writing happens on the networking thread
+            if let Err(err) = client.connection.send(*encoding, messages) {
+                error!(
+                    "Failed to send messages to client {:?}: {}",
+                    client.get_address(),
+                    err
+                );
+                commands.trigger(ClientDisconnectedTrigger(entity));
             }
-        } else {
-            // Is a dummy client
         }
     }
 }
@@ -38,7 +41,8 @@ pub fn sending_immediate_messages(
 pub fn sending_client_messages(
     trigger: Trigger<SendOutgoingMessagesTrigger>,
     lobby_management: LobbyManagementSystemParam,
-    mut connected_clients: Query<(&mut MyNetworkClient, &mut OutMessageQueue)>,
+    mut connected_clients: Query<(&MyNetworkClient, &MessageEncoding, &mut OutMessageQueue)>,
+    mut commands: Commands,
 ) {
     let lobby = trigger.entity();
 
@@ -51,30 +55,20 @@ pub fn sending_client_messages(
                 .get_lobby_gamestate(lobby)
                 .expect("Failed to get game state");
             for player in clients_in_lobby {
-                let (mut client, mut out_message_queue) = connected_clients
+                let (client, encoding, mut out_message_queue) = connected_clients
                     .get_mut(player)
                     .expect("Failed to get client");
 
                 let mut messages: Vec<_> = out_message_queue.drain(..).collect();
-                if let Some(stream) = &mut client.stream {
-                    for message in &mut messages {
-                        message.tick_sent = game_state.tick;
-                    }
-
-                    if !messages.is_empty() {
-                        let messages =
-                            serde_json::to_vec(&messages).expect("Failed to serialize messages");
-
-                        let length = (messages.len() as u32).to_le_bytes();
+                for message in &mut messages {
+                    message.tick_sent = game_state.tick;
+                }
 
-                        if let Err(err) = stream.write_all(&length) {
-                            error!("Failed to send length to client: {}", err);
-                        } else if let Err(err) = stream.write_all(&messages) {
-                            error!("Failed to send messages to client: {}", err);
-                        }
+                if !messages.is_empty() {
+                    if let Err(err) = client.connection.send(*encoding, messages) {
+                        error!("Failed to send messages to client: {}", err);
+                        commands.trigger(ClientDisconnectedTrigger(player));
                     }
-                } else {
-                    // Is a dummy client
                 }
             }
         }
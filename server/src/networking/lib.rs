@@ -1,9 +1,28 @@
-use std::net::TcpListener;
-
 use bevy::prelude::*;
+use crossbeam_channel::{Receiver, Sender};
+
+use super::{
+    networking_thread::{connect, ClientConnection, ConnectionWorker},
+    transport::Transport,
+};
 
-// A simple resource holding the listener
+/// Handle to the thread that does all the socket I/O, see `spawn_networking_thread`
+/// Dropping it stops the thread
 #[derive(Resource)]
-pub struct MyTcpListener {
-    pub listener: TcpListener,
+pub struct MyNetworkingThread {
+    /// The connections accepted by the networking thread, waiting to be spawned as clients
+    pub new_connections: Receiver<ClientConnection>,
+    pub(super) workers: Sender<ConnectionWorker>,
+}
+
+impl MyNetworkingThread {
+    /// Hands a transport created by the server itself (e.g. for dummies or in-memory bots) to the networking thread
+    pub fn connect(&self, transport: impl Transport + 'static) -> ClientConnection {
+        let (connection, worker) = connect(Box::new(transport));
+        self.workers
+            .send(worker)
+            .expect("Failed to hand connection to networking thread");
+
+        connection
+    }
 }
@@ -3,15 +3,21 @@ use shared::{
     asset_handling::config::ServerConfigSystemParam,
     game::player_handling::TankBodyMarker,
     networking::{
+        framing::PROTOCOL_VERSION,
         lobby_management::{
             lobby_management::LobbyManagementSystemParam, AwaitingFirstContact,
-            PlayerWantsToJoinLobbyTrigger,
+            PlayerWantsToJoinLobbyTrigger, SkillRating,
         },
+        message_codec::MessageEncoding,
         messages::{
             message_container::{
                 FirstContactTrigger, MessageContainer, MessageTarget, NetworkMessageType,
             },
-            message_data::{first_contact::ClientType, message_error_types::ErrorMessageTypes},
+            message_data::{
+                first_contact::ClientType,
+                message_error_types::ErrorMessageTypes,
+                protocol_negotiation::{NegotiatedProtocol, ProtocolCapability},
+            },
             message_queue::ImmediateOutMessageQueue,
         },
     },
@@ -19,6 +25,14 @@ use shared::{
 
 use crate::networking::handle_clients::lib::{ClientDisconnectedTrigger, MyNetworkClient};
 
+use super::reconnect::ReconnectClientTrigger;
+
+/// The optional capabilities this server supports
+const SUPPORTED_CAPABILITIES: &[ProtocolCapability] = &[
+    ProtocolCapability::BinaryEncoding,
+    ProtocolCapability::DeltaStates,
+];
+
 pub fn handle_awaiting_first_contact(
     mut commands: Commands,
     mut clients: Query<(Entity, &mut AwaitingFirstContact)>,
@@ -47,6 +61,59 @@ pub fn handle_first_contact_message(
 
     // Update the client's state
     if let Ok((client_entity, mut client, mut message_queue)) = clients.get_mut(sender) {
+        if message.protocol_version != Some(PROTOCOL_VERSION) {
+            error!(
+                "Client {:?} speaks protocol version {:?}, but the server speaks {}",
+                client_entity, message.protocol_version, PROTOCOL_VERSION
+            );
+            message_queue.push_back(MessageContainer::new(
+                MessageTarget::Client(client_entity),
+                NetworkMessageType::MessageError(ErrorMessageTypes::UnsupportedProtocolVersion(
+                    format!(
+                        "Protocol version {:?} is not supported, the server speaks version {}",
+                        message.protocol_version, PROTOCOL_VERSION
+                    ),
+                )),
+            ));
+
+            return;
+        }
+
+        // Only use the capabilities both sides support
+        let negotiated_protocol = NegotiatedProtocol {
+            protocol_version: PROTOCOL_VERSION,
+            capabilities: message
+                .capabilities
+                .iter()
+                .filter(|capability| SUPPORTED_CAPABILITIES.contains(capability))
+                .cloned()
+                .collect(),
+        };
+
+        // Reconnecting clients take over their old entity instead of joining a lobby
+        if let Some(session_token) = &message.session_token {
+            commands.trigger_targets(
+                ReconnectClientTrigger {
+                    session_token: session_token.clone(),
+                    negotiated_protocol,
+                },
+                client_entity,
+            );
+
+            return;
+        }
+
+        message_queue.push_back(MessageContainer::new(
+            MessageTarget::Client(client_entity),
+            NetworkMessageType::ProtocolNegotiated(negotiated_protocol.clone()),
+        ));
+        if negotiated_protocol.has_capability(&ProtocolCapability::BinaryEncoding) {
+            commands
+                .entity(client_entity)
+                .insert(MessageEncoding::MessagePack);
+        }
+        commands.entity(client_entity).insert(negotiated_protocol);
+
         client.name = Some(message.bot_name.clone());
         if let Some(assigned_spawn_point) = message.bot_assigned_spawn_point {
             client.assigned_spawn_point = Some(assigned_spawn_point);
@@ -55,6 +122,11 @@ pub fn handle_first_contact_message(
         commands
             .entity(client_entity)
             .insert(message.client_type.clone());
+        if let Some(skill_rating) = message.skill_rating {
+            commands
+                .entity(client_entity)
+                .insert(SkillRating(skill_rating));
+        }
 
         match message.client_type {
             ClientType::Player => {
@@ -79,12 +151,7 @@ pub fn handle_first_contact_message(
     }
 
     // get or insert lobby
-    match lobby_management.get_or_insert_lobby_entity(
-        &message.lobby_name,
-        message.map_name.as_deref(),
-        &mut commands,
-        server_config,
-    ) {
+    match lobby_management.get_or_insert_lobby_entity(message, &mut commands, server_config) {
         Ok(lobby_entity) => {
             commands.trigger(PlayerWantsToJoinLobbyTrigger {
                 player: sender,
@@ -92,6 +159,10 @@ pub fn handle_first_contact_message(
                 player_type: message.client_type.clone(),
                 team_name: message.team_name.clone(),
                 player_name: message.bot_name.clone(),
+                lobby_password: message.lobby_password.clone(),
+                team_join_code: message.team_join_code.clone(),
+                is_admin: server_config.admin_password.is_some()
+                    && message.admin_password == server_config.admin_password,
             });
         }
         Err(e) => {
@@ -0,0 +1,89 @@
+use bevy::prelude::*;
+use shared::{
+    asset_handling::config::{ServerConfigSystemParam, TankConfigSystemParam},
+    game::tank_types::TankType,
+    networking::{
+        lobby_management::{lobby_management::LobbyManagementSystemParam, InLobby, LobbyState},
+        messages::{
+            message_container::{MessageContainer, MessageTarget, NetworkMessageType},
+            message_data::{first_contact::ClientType, protocol_negotiation::NegotiatedProtocol},
+            message_queue::ImmediateOutMessageQueue,
+        },
+    },
+};
+
+use crate::{
+    gameplay::{
+        delta_game_states::SentGameStates,
+        game_state_handling::game_state_message,
+        start_lobby::{game_starts_for_client, get_connected_configs_in_lobby},
+    },
+    networking::handle_clients::lib::MyNetworkClient,
+};
+
+/// Spectators joining a running match get the game config and the current state right away,
+/// afterwards they receive the game state each tick like everyone else
+pub fn send_running_game_to_spectator(
+    trigger: Trigger<OnAdd, InLobby>,
+    clients: Query<(&MyNetworkClient, &ClientType, Option<&TankType>)>,
+    in_lobby: Query<&InLobby>,
+    mut spectators: Query<(
+        &mut ImmediateOutMessageQueue,
+        &mut SentGameStates,
+        Option<&NegotiatedProtocol>,
+    )>,
+    lobby_management: LobbyManagementSystemParam,
+    server_config: ServerConfigSystemParam,
+    tank_config: TankConfigSystemParam,
+) {
+    let spectator = trigger.entity();
+    // Projectiles and mines are in a lobby as well
+    let Ok((_, client_type, _)) = clients.get(spectator) else {
+        return;
+    };
+    if *client_type != ClientType::Spectator {
+        return;
+    }
+
+    let lobby_entity = **in_lobby
+        .get(spectator)
+        .expect("Failed to get lobby of client");
+    let lobby = lobby_management
+        .get_lobby(lobby_entity)
+        .expect("Failed to get lobby");
+    if lobby.state != LobbyState::InProgress {
+        return;
+    }
+    let lobby_state = lobby_management
+        .get_lobby_gamestate(lobby_entity)
+        .expect("Failed to get lobby game state");
+
+    info!(
+        "Spectator {:?} joined running lobby \"{}\", sending the game",
+        spectator, lobby.lobby_name
+    );
+
+    let connected_clients =
+        get_connected_configs_in_lobby(&lobby_management, lobby_entity, &clients);
+    let (mut message_queue, sent_game_states, protocol) = spectators
+        .get_mut(spectator)
+        .expect("Failed to get spectator");
+
+    message_queue.push_back(MessageContainer::new(
+        MessageTarget::Client(spectator),
+        NetworkMessageType::GameStarts(game_starts_for_client(
+            spectator,
+            lobby,
+            connected_clients,
+            server_config.server_config(),
+            tank_config.tank_configs(),
+        )),
+    ));
+    message_queue.push_back(MessageContainer::new(
+        MessageTarget::Client(spectator),
+        game_state_message(
+            lobby_state.clone().into(),
+            Some((sent_game_states, protocol)),
+        ),
+    ));
+}
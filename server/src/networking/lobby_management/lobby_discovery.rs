@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+use shared::{
+    asset_handling::maps::MapConfigSystemParam,
+    networking::{
+        lobby_management::{lobby_management::LobbyManagementSystemParam, MyLobby},
+        messages::{
+            message_container::{
+                MessageContainer, MessageTarget, NetworkMessageType, RequestLobbyListTrigger,
+            },
+            message_data::lobby_list::{LobbyInfo, LobbyList, TeamInfo},
+            message_queue::ImmediateOutMessageQueue,
+        },
+    },
+};
+
+/// Answers with all lobbies and maps, so clients don't have to guess their names
+pub fn handle_request_lobby_list(
+    trigger: Trigger<RequestLobbyListTrigger>,
+    lobby_management: LobbyManagementSystemParam,
+    map_config: MapConfigSystemParam,
+    mut message_queues: Query<&mut ImmediateOutMessageQueue>,
+) {
+    let sender = trigger.sender.expect("Message sender not found");
+
+    let mut lobbies = lobby_management
+        .lobby_resource
+        .lobbies
+        .values()
+        .filter_map(|&lobby_entity| lobby_management.get_lobby(lobby_entity).ok())
+        .map(lobby_info)
+        .collect::<Vec<_>>();
+    lobbies.sort_by(|a, b| a.lobby_name.cmp(&b.lobby_name));
+
+    let mut maps = map_config.list_map_names();
+    maps.sort();
+
+    message_queues
+        .get_mut(sender)
+        .expect("Failed to get client message queue")
+        .push_back(MessageContainer::new(
+            MessageTarget::Client(sender),
+            NetworkMessageType::LobbyList(LobbyList { lobbies, maps }),
+        ));
+}
+
+fn lobby_info(lobby: &MyLobby) -> LobbyInfo {
+    // The teams are only known once the map config of the lobby was set up
+    let mut teams = lobby
+        .map_config
+        .as_ref()
+        .map(|map_config| {
+            map_config
+                .teams
+                .iter()
+                .map(|(team_name, team)| TeamInfo {
+                    team_name: team_name.clone(),
+                    players: team.players.len(),
+                    max_players: team.max_players,
+                    has_join_code: lobby.team_join_codes.contains_key(team_name),
+                })
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    teams.sort_by(|a, b| a.team_name.cmp(&b.team_name));
+
+    LobbyInfo {
+        lobby_name: lobby.lobby_name.clone(),
+        state: lobby.state.clone(),
+        map_name: lobby.map_name.clone(),
+        teams,
+        spectators: lobby.spectators.len(),
+        has_password: lobby.password.is_some(),
+    }
+}
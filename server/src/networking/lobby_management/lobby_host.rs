@@ -0,0 +1,205 @@
+use bevy::prelude::*;
+use shared::networking::{
+    lobby_management::{
+        lobby_management::LobbyManagementSystemParam, LobbyRosterChangedTrigger, LobbyState,
+    },
+    message_codec::MessageEncoding,
+    messages::{
+        message_container::{
+            ChangeLobbySettingsTrigger, KickMemberTrigger, MessageContainer, MessageTarget,
+            NetworkMessageType, TransferHostTrigger,
+        },
+        message_data::{
+            first_contact::ClientType,
+            lobby_membership::{LobbyRemovalReason, MemberLeftLobby, RemovedFromLobby},
+            message_error_types::ErrorMessageTypes,
+        },
+        message_queue::ImmediateOutMessageQueue,
+    },
+};
+
+use crate::networking::handle_clients::lib::{ClientDisconnectedTrigger, MyNetworkClient};
+
+use super::{
+    lobby_membership::{
+        check_membership_can_change, client_name, get_members, notify_members, send_error,
+    },
+    reconnect::ClientSessionToken,
+};
+
+pub fn handle_kick_member(
+    trigger: Trigger<KickMemberTrigger>,
+    mut lobby_management: LobbyManagementSystemParam,
+    clients: Query<&MyNetworkClient>,
+    encodings: Query<&MessageEncoding>,
+    mut message_queues: Query<&mut ImmediateOutMessageQueue>,
+    mut commands: Commands,
+) {
+    let lobby_entity = trigger.entity();
+    let sender = trigger.sender.expect("Message sender not found");
+    let kicked = trigger.client_id;
+
+    if let Err(e) = check_is_host(&lobby_management, lobby_entity, sender)
+        .and_then(|_| check_is_other_member(&lobby_management, lobby_entity, sender, kicked))
+    {
+        send_error(&mut message_queues, sender, e);
+        return;
+    }
+
+    let lobby = lobby_management
+        .get_lobby(lobby_entity)
+        .expect("Failed to get lobby");
+    info!(
+        "Host {:?} kicked {:?} from lobby \"{}\"",
+        sender, kicked, lobby.lobby_name
+    );
+
+    match lobby.state {
+        LobbyState::SettingUp | LobbyState::ReadyToStart => {
+            // Collected before removing the client, so it gets notified as well
+            let members = get_members(&lobby_management, lobby_entity);
+            lobby_management.remove_player_from_lobby(
+                kicked,
+                lobby_entity,
+                LobbyRemovalReason::Kicked,
+                &mut commands,
+            );
+
+            notify_members(
+                &mut message_queues,
+                members,
+                NetworkMessageType::MemberLeftLobby(MemberLeftLobby {
+                    client_id: kicked,
+                    client_name: client_name(&clients, kicked),
+                }),
+            );
+        }
+        LobbyState::InProgress | LobbyState::Finished => {
+            // Sent on the connection directly, the message queues are gone once the client is disconnected
+            if let (Ok(client), Ok(encoding)) = (clients.get(kicked), encodings.get(kicked)) {
+                let removed_from_lobby = MessageContainer::new(
+                    MessageTarget::Client(kicked),
+                    NetworkMessageType::RemovedFromLobby(RemovedFromLobby {
+                        lobby_name: lobby.lobby_name.clone(),
+                        reason: LobbyRemovalReason::Kicked,
+                    }),
+                );
+                if let Err(err) = client.connection.send(*encoding, vec![removed_from_lobby]) {
+                    error!("Failed to send messages to client {:?}: {}", kicked, err);
+                }
+            }
+
+            // Without a session token, the client can't come back into the match
+            commands.entity(kicked).remove::<ClientSessionToken>();
+            commands.trigger(ClientDisconnectedTrigger(kicked));
+        }
+    }
+}
+
+pub fn handle_transfer_host(
+    trigger: Trigger<TransferHostTrigger>,
+    mut lobby_management: LobbyManagementSystemParam,
+    mut message_queues: Query<&mut ImmediateOutMessageQueue>,
+    mut commands: Commands,
+) {
+    let lobby_entity = trigger.entity();
+    let sender = trigger.sender.expect("Message sender not found");
+    let new_host = trigger.client_id;
+
+    if let Err(e) = check_is_host(&lobby_management, lobby_entity, sender)
+        .and_then(|_| check_is_other_member(&lobby_management, lobby_entity, sender, new_host))
+    {
+        send_error(&mut message_queues, sender, e);
+        return;
+    }
+
+    let mut lobby = lobby_management
+        .get_lobby_mut(lobby_entity)
+        .expect("Failed to get lobby");
+    lobby.host = Some(new_host);
+    info!(
+        "Host {:?} made {:?} the host of lobby \"{}\"",
+        sender, new_host, lobby.lobby_name
+    );
+
+    commands.trigger_targets(LobbyRosterChangedTrigger, lobby_entity);
+}
+
+pub fn handle_change_lobby_settings(
+    trigger: Trigger<ChangeLobbySettingsTrigger>,
+    mut lobby_management: LobbyManagementSystemParam,
+    mut message_queues: Query<&mut ImmediateOutMessageQueue>,
+) {
+    let lobby_entity = trigger.entity();
+    let sender = trigger.sender.expect("Message sender not found");
+
+    if let Err(e) = check_is_host(&lobby_management, lobby_entity, sender)
+        .and_then(|_| check_membership_can_change(&lobby_management, lobby_entity))
+    {
+        send_error(&mut message_queues, sender, e);
+        return;
+    }
+
+    let mut lobby = lobby_management
+        .get_lobby_mut(lobby_entity)
+        .expect("Failed to get lobby");
+    lobby.password = trigger.password.clone();
+    lobby.team_join_codes = trigger.team_join_codes.clone();
+    lobby.auto_start = trigger.auto_start.clone();
+    info!(
+        "Host {:?} changed the settings of lobby \"{}\"",
+        sender, lobby.lobby_name
+    );
+}
+
+pub fn check_is_host(
+    lobby_management: &LobbyManagementSystemParam,
+    lobby_entity: Entity,
+    client: Entity,
+) -> Result<(), ErrorMessageTypes> {
+    let lobby = lobby_management
+        .get_lobby(lobby_entity)
+        .expect("Failed to get lobby");
+
+    if lobby.is_host(client) {
+        Ok(())
+    } else {
+        Err(ErrorMessageTypes::NotLobbyHost(format!(
+            "Only the host of lobby \"{}\" can do this",
+            lobby.lobby_name
+        )))
+    }
+}
+
+fn check_is_other_member(
+    lobby_management: &LobbyManagementSystemParam,
+    lobby_entity: Entity,
+    sender: Entity,
+    client: Entity,
+) -> Result<(), ErrorMessageTypes> {
+    let lobby = lobby_management
+        .get_lobby(lobby_entity)
+        .expect("Failed to get lobby");
+
+    if client == sender {
+        Err(ErrorMessageTypes::LobbyManagementError(
+            "The host can't target itself".to_string(),
+        ))
+    } else if !lobby.is_member(client) {
+        Err(ErrorMessageTypes::LobbyManagementError(format!(
+            "Client {:?} is not a member of lobby \"{}\"",
+            client, lobby.lobby_name
+        )))
+    } else if lobby
+        .players
+        .iter()
+        .any(|(_, player, client_type)| *player == client && *client_type == ClientType::Dummy)
+    {
+        Err(ErrorMessageTypes::LobbyManagementError(format!(
+            "Client {:?} is a dummy, it can't be kicked or become the host",
+            client
+        )))
+    } else {
+        Ok(())
+    }
+}
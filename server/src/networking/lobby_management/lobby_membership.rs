@@ -0,0 +1,259 @@
+use bevy::prelude::*;
+use shared::networking::{
+    lobby_management::{
+        lobby_management::{LobbyManagementArgument, LobbyManagementSystemParam},
+        InTeam, LobbyRosterChangedTrigger, LobbyState,
+    },
+    messages::{
+        message_container::{
+            LeaveLobbyTrigger, MessageContainer, MessageTarget, NetworkMessageType,
+            SetReadyTrigger, StartGameTrigger, SwitchTeamTrigger,
+        },
+        message_data::{
+            lobby_membership::{LobbyRemovalReason, MemberLeftLobby, MemberSwitchedTeam},
+            message_error_types::ErrorMessageTypes,
+            start_game_config::StartGameConfig,
+        },
+        message_queue::ImmediateOutMessageQueue,
+    },
+};
+
+use crate::networking::handle_clients::lib::MyNetworkClient;
+
+pub fn handle_leave_lobby(
+    trigger: Trigger<LeaveLobbyTrigger>,
+    mut lobby_management: LobbyManagementSystemParam,
+    clients: Query<&MyNetworkClient>,
+    mut message_queues: Query<&mut ImmediateOutMessageQueue>,
+    mut commands: Commands,
+) {
+    let lobby_entity = trigger.entity();
+    let sender = trigger.sender.expect("Message sender not found");
+
+    if let Err(e) = check_membership_can_change(&lobby_management, lobby_entity) {
+        send_error(&mut message_queues, sender, e);
+        return;
+    }
+
+    // Collected before removing the client, so it gets notified as well
+    let members = get_members(&lobby_management, lobby_entity);
+    lobby_management.remove_player_from_lobby(
+        sender,
+        lobby_entity,
+        LobbyRemovalReason::Left,
+        &mut commands,
+    );
+    info!("Client {:?} left lobby {:?}", sender, lobby_entity);
+
+    notify_members(
+        &mut message_queues,
+        members,
+        NetworkMessageType::MemberLeftLobby(MemberLeftLobby {
+            client_id: sender,
+            client_name: client_name(&clients, sender),
+        }),
+    );
+}
+
+pub fn handle_switch_team(
+    trigger: Trigger<SwitchTeamTrigger>,
+    mut lobby_management: LobbyManagementSystemParam,
+    clients: Query<&MyNetworkClient>,
+    mut message_queues: Query<&mut ImmediateOutMessageQueue>,
+    mut commands: Commands,
+) {
+    let lobby_entity = trigger.entity();
+    let sender = trigger.sender.expect("Message sender not found");
+    let team_name = &trigger.team_name;
+
+    if let Err(e) = check_membership_can_change(&lobby_management, lobby_entity) {
+        send_error(&mut message_queues, sender, e);
+        return;
+    }
+
+    let mut lobby = lobby_management
+        .get_lobby_mut(lobby_entity)
+        .expect("Failed to get lobby");
+    if !lobby.players.iter().any(|(_, player, _)| *player == sender) {
+        send_error(
+            &mut message_queues,
+            sender,
+            ErrorMessageTypes::LobbyManagementError("Only players can switch teams".to_string()),
+        );
+        return;
+    }
+    if let Some(expected_join_code) = lobby.team_join_codes.get(team_name) {
+        if trigger.team_join_code.as_ref() != Some(expected_join_code) {
+            send_error(
+                &mut message_queues,
+                sender,
+                ErrorMessageTypes::WrongTeamJoinCode(format!(
+                    "Team {} can't be joined without the correct join code",
+                    team_name
+                )),
+            );
+            return;
+        }
+    }
+
+    let map_config = lobby
+        .map_config
+        .as_mut()
+        .expect("Map config should be set up by now");
+    let previous_team = map_config
+        .get_team_of_player(sender)
+        .map(|(previous_team, _)| previous_team);
+    if previous_team.as_ref() == Some(team_name) {
+        return;
+    }
+
+    map_config.remove_player_from_team(sender);
+    if let Err(e) = map_config.insert_player_into_team(team_name, sender) {
+        error!(
+            "Failed to switch player {:?} to team {}: {:?}",
+            sender, team_name, e
+        );
+        // The player just left it, so there is room in the previous team
+        if let Some(previous_team) = previous_team {
+            map_config
+                .insert_player_into_team(&previous_team, sender)
+                .expect("Failed to put player back into its previous team");
+        }
+        send_error(&mut message_queues, sender, e);
+        return;
+    }
+
+    commands.entity(sender).insert(InTeam(team_name.clone()));
+    info!("Player {:?} switched to team {}", sender, team_name);
+    commands.trigger_targets(LobbyRosterChangedTrigger, lobby_entity);
+
+    notify_members(
+        &mut message_queues,
+        get_members(&lobby_management, lobby_entity),
+        NetworkMessageType::MemberSwitchedTeam(MemberSwitchedTeam {
+            client_id: sender,
+            client_name: client_name(&clients, sender),
+            team_name: team_name.clone(),
+        }),
+    );
+}
+
+pub fn handle_set_ready(
+    trigger: Trigger<SetReadyTrigger>,
+    mut lobby_management: LobbyManagementSystemParam,
+    mut message_queues: Query<&mut ImmediateOutMessageQueue>,
+    mut commands: Commands,
+) {
+    let lobby_entity = trigger.entity();
+    let sender = trigger.sender.expect("Message sender not found");
+
+    if let Err(e) = check_membership_can_change(&lobby_management, lobby_entity) {
+        send_error(&mut message_queues, sender, e);
+        return;
+    }
+
+    let mut lobby = lobby_management
+        .get_lobby_mut(lobby_entity)
+        .expect("Failed to get lobby");
+    if !lobby.players.iter().any(|(_, player, _)| *player == sender) {
+        send_error(
+            &mut message_queues,
+            sender,
+            ErrorMessageTypes::LobbyManagementError("Only players can be ready".to_string()),
+        );
+        return;
+    }
+
+    lobby.set_ready(sender, trigger.ready);
+    commands.trigger_targets(LobbyRosterChangedTrigger, lobby_entity);
+
+    if let Some(auto_start) = &lobby.auto_start {
+        if lobby.all_players_ready() {
+            info!(
+                "All players in lobby \"{}\" are ready, starting",
+                lobby.lobby_name
+            );
+            commands.trigger_targets(
+                StartGameTrigger {
+                    // Everyone is ready, so there is nothing to force
+                    message: StartGameConfig {
+                        force: false,
+                        ..auto_start.clone()
+                    },
+                    sender: Some(sender),
+                    tick_sent: trigger.tick_sent,
+                },
+                lobby_entity,
+            );
+        }
+    }
+}
+
+/// Members can only come and go as they like until the game starts
+pub fn check_membership_can_change(
+    lobby_management: &LobbyManagementSystemParam,
+    lobby_entity: Entity,
+) -> Result<(), ErrorMessageTypes> {
+    let lobby = lobby_management
+        .get_lobby(lobby_entity)
+        .expect("Failed to get lobby");
+
+    match lobby.state {
+        LobbyState::SettingUp | LobbyState::ReadyToStart => Ok(()),
+        LobbyState::InProgress | LobbyState::Finished => {
+            Err(ErrorMessageTypes::LobbyAlreadyRunning(format!(
+                "Members can't change while the lobby is in state {:?}",
+                lobby.state
+            )))
+        }
+    }
+}
+
+pub fn get_members(
+    lobby_management: &LobbyManagementSystemParam,
+    lobby_entity: Entity,
+) -> Vec<Entity> {
+    lobby_management
+        .targets_get_players_and_spectators_in_lobby(LobbyManagementArgument {
+            lobby: Some(lobby_entity),
+            ..default()
+        })
+        .unwrap_or_default()
+}
+
+pub fn client_name(clients: &Query<&MyNetworkClient>, client: Entity) -> String {
+    clients
+        .get(client)
+        .ok()
+        .and_then(|client| client.name.clone())
+        .unwrap_or_default()
+}
+
+pub fn notify_members(
+    message_queues: &mut Query<&mut ImmediateOutMessageQueue>,
+    members: Vec<Entity>,
+    message: NetworkMessageType,
+) {
+    for member in members {
+        if let Ok(mut message_queue) = message_queues.get_mut(member) {
+            message_queue.push_back(MessageContainer::new(
+                MessageTarget::Client(member),
+                message.clone(),
+            ));
+        }
+    }
+}
+
+pub fn send_error(
+    message_queues: &mut Query<&mut ImmediateOutMessageQueue>,
+    client: Entity,
+    error: ErrorMessageTypes,
+) {
+    message_queues
+        .get_mut(client)
+        .expect("Failed to get client message queue")
+        .push_back(MessageContainer::new(
+            MessageTarget::Client(client),
+            NetworkMessageType::MessageError(error),
+        ));
+}
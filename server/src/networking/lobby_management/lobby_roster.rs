@@ -0,0 +1,95 @@
+use bevy::prelude::*;
+use shared::{
+    game::tank_types::TankType,
+    networking::{
+        lobby_management::{
+            lobby_management::{LobbyManagementArgument, LobbyManagementSystemParam},
+            LobbyRosterChangedTrigger, MyLobby,
+        },
+        messages::{
+            message_container::{MessageContainer, MessageTarget, NetworkMessageType},
+            message_data::{
+                first_contact::ClientType,
+                lobby_roster::{LobbyMember, LobbyRoster},
+            },
+            message_queue::ImmediateOutMessageQueue,
+        },
+    },
+};
+
+use crate::networking::handle_clients::lib::MyNetworkClient;
+
+pub fn send_lobby_roster(
+    trigger: Trigger<LobbyRosterChangedTrigger>,
+    lobby_management: LobbyManagementSystemParam,
+    clients: Query<(&MyNetworkClient, &ClientType, Option<&TankType>)>,
+    mut message_queues: Query<&mut ImmediateOutMessageQueue>,
+) {
+    let lobby_entity = trigger.entity();
+    let lobby = match lobby_management.get_lobby(lobby_entity) {
+        Ok(lobby) => lobby,
+        // The last member left and the lobby got removed
+        Err(_) => return,
+    };
+
+    let roster = lobby_roster(lobby, &clients);
+    let members = lobby_management
+        .targets_get_players_and_spectators_in_lobby(LobbyManagementArgument {
+            lobby: Some(lobby_entity),
+            ..default()
+        })
+        .unwrap_or_default();
+    for member in members {
+        if let Ok(mut message_queue) = message_queues.get_mut(member) {
+            message_queue.push_back(MessageContainer::new(
+                MessageTarget::Client(member),
+                NetworkMessageType::LobbyRoster(roster.clone()),
+            ));
+        }
+    }
+}
+
+pub fn lobby_roster(
+    lobby: &MyLobby,
+    clients: &Query<(&MyNetworkClient, &ClientType, Option<&TankType>)>,
+) -> LobbyRoster {
+    let players = lobby.players.iter().map(|(name, player, client_type)| {
+        let team_name = lobby
+            .map_config
+            .as_ref()
+            .and_then(|map_config| map_config.get_team_of_player(*player))
+            .map(|(team_name, _)| team_name);
+
+        LobbyMember {
+            client_id: *player,
+            client_name: name.clone(),
+            client_type: client_type.clone(),
+            team_name,
+            tank_type: clients
+                .get(*player)
+                .ok()
+                .and_then(|(_, _, tank_type)| tank_type.cloned()),
+            ready: lobby.is_ready(*player),
+        }
+    });
+    let spectators = lobby.spectators.iter().map(|spectator| LobbyMember {
+        client_id: *spectator,
+        client_name: clients
+            .get(*spectator)
+            .ok()
+            .and_then(|(client, _, _)| client.name.clone())
+            .unwrap_or_default(),
+        client_type: ClientType::Spectator,
+        team_name: None,
+        tank_type: None,
+        ready: false,
+    });
+
+    LobbyRoster {
+        lobby_name: lobby.lobby_name.clone(),
+        state: lobby.state.clone(),
+        map_name: lobby.map_name.clone(),
+        host: lobby.host,
+        members: players.chain(spectators).collect(),
+    }
+}
@@ -1,16 +1,55 @@
 use bevy::prelude::*;
 use handle_first_contact::{handle_awaiting_first_contact, handle_first_contact_message};
-use shared::networking::networking_state::MyNetworkingState;
+use late_spectators::send_running_game_to_spectator;
+use lobby_discovery::handle_request_lobby_list;
+use lobby_host::{handle_change_lobby_settings, handle_kick_member, handle_transfer_host};
+use lobby_membership::{handle_leave_lobby, handle_set_ready, handle_switch_team};
+use lobby_roster::send_lobby_roster;
+use reconnect::{
+    handle_awaiting_reconnect, handle_reconnect, issue_session_token, send_game_starts_on_reconnect,
+};
+use rematch::handle_rematch;
+use shared::networking::{lobby_management::MyLobby, networking_state::MyNetworkingState};
 
 pub mod handle_first_contact;
+pub mod late_spectators;
+pub mod lobby_discovery;
+pub mod lobby_host;
+pub mod lobby_membership;
+pub mod lobby_roster;
+pub mod reconnect;
+pub mod rematch;
 
 pub struct MyLobbyManagementPlugin;
 
 impl Plugin for MyLobbyManagementPlugin {
     fn build(&self, app: &mut App) {
-        app.add_observer(handle_first_contact_message).add_systems(
-            Update,
-            (handle_awaiting_first_contact.run_if(in_state(MyNetworkingState::Running)),),
-        );
+        app.add_observer(handle_first_contact_message)
+            .add_observer(handle_request_lobby_list)
+            .add_observer(handle_reconnect)
+            .add_observer(issue_session_token)
+            .add_observer(send_game_starts_on_reconnect)
+            .add_observer(send_running_game_to_spectator)
+            .add_observer(add_observers_to_lobby)
+            .add_systems(
+                Update,
+                (
+                    handle_awaiting_first_contact.run_if(in_state(MyNetworkingState::Running)),
+                    handle_awaiting_reconnect.run_if(in_state(MyNetworkingState::Running)),
+                ),
+            );
     }
 }
+
+fn add_observers_to_lobby(trigger: Trigger<OnAdd, MyLobby>, mut commands: Commands) {
+    commands
+        .entity(trigger.entity())
+        .observe(handle_leave_lobby)
+        .observe(handle_switch_team)
+        .observe(handle_set_ready)
+        .observe(handle_kick_member)
+        .observe(handle_transfer_host)
+        .observe(handle_change_lobby_settings)
+        .observe(handle_rematch)
+        .observe(send_lobby_roster);
+}
@@ -0,0 +1,207 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use shared::{
+    asset_handling::config::{ServerConfigSystemParam, TankConfigSystemParam},
+    game::tank_types::TankType,
+    networking::{
+        lobby_management::{lobby_management::LobbyManagementSystemParam, InLobby},
+        message_codec::MessageEncoding,
+        messages::{
+            message_container::{MessageContainer, MessageTarget, NetworkMessageType},
+            message_data::{
+                first_contact::ClientType,
+                message_error_types::ErrorMessageTypes,
+                protocol_negotiation::{NegotiatedProtocol, ProtocolCapability},
+                session_token::SessionToken,
+            },
+            message_queue::ImmediateOutMessageQueue,
+        },
+    },
+};
+
+use crate::{
+    gameplay::start_lobby::{game_starts_for_client, get_connected_configs_in_lobby},
+    networking::handle_clients::lib::{ClientDisconnectedTrigger, MyNetworkClient},
+};
+
+/// The token a player can reconnect with, issued when joining a lobby
+#[derive(Debug, Component, Clone, PartialEq, Deref)]
+pub struct ClientSessionToken(pub String);
+
+impl ClientSessionToken {
+    pub fn generate() -> Self {
+        Self(format!("{:032x}", rand::random::<u128>()))
+    }
+}
+
+/// Added to a player that lost its connection during a match, its tank idles until it reconnects
+/// The player is removed from the lobby once the timer finishes
+#[derive(Debug, Component, Deref, DerefMut)]
+pub struct AwaitingReconnect(pub Timer);
+
+impl AwaitingReconnect {
+    pub fn new(time_millis: u64) -> Self {
+        Self(Timer::new(
+            Duration::from_millis(time_millis),
+            TimerMode::Once,
+        ))
+    }
+}
+
+/// Triggered on a player once it took over the connection of a reconnecting client
+#[derive(Debug, Event)]
+pub struct ClientReconnectedTrigger;
+
+/// Triggered on a new client that sent a session token in its first contact
+#[derive(Debug, Event)]
+pub struct ReconnectClientTrigger {
+    pub session_token: String,
+    pub negotiated_protocol: NegotiatedProtocol,
+}
+
+/// Issues a session token to every player joining a lobby
+pub fn issue_session_token(
+    trigger: Trigger<OnAdd, InLobby>,
+    mut clients: Query<(&ClientType, &mut ImmediateOutMessageQueue), With<MyNetworkClient>>,
+    mut commands: Commands,
+) {
+    let client_entity = trigger.entity();
+    // Projectiles and mines are in a lobby as well
+    let Ok((client_type, mut message_queue)) = clients.get_mut(client_entity) else {
+        return;
+    };
+    // Spectators don't have a tank to return to, dummies don't have a connection to reconnect with
+    if *client_type != ClientType::Player {
+        return;
+    }
+
+    let session_token = ClientSessionToken::generate();
+    message_queue.push_back(MessageContainer::new(
+        MessageTarget::Client(client_entity),
+        NetworkMessageType::SessionToken(SessionToken {
+            token: session_token.0.clone(),
+        }),
+    ));
+    commands.entity(client_entity).insert(session_token);
+}
+
+pub fn handle_awaiting_reconnect(
+    mut commands: Commands,
+    mut clients: Query<(Entity, &mut AwaitingReconnect)>,
+    time: Res<Time>,
+) {
+    for (entity, mut timer) in clients.iter_mut() {
+        if timer.0.tick(time.delta()).finished() {
+            warn!("Client {:?} did not reconnect in time", entity);
+            commands.trigger(ClientDisconnectedTrigger(entity));
+        }
+    }
+}
+
+/// Hands the connection of the new client to the player it reconnects as, the new client entity is despawned
+pub fn handle_reconnect(
+    trigger: Trigger<ReconnectClientTrigger>,
+    mut clients: Query<&mut MyNetworkClient>,
+    awaiting_clients: Query<(Entity, &ClientSessionToken), With<AwaitingReconnect>>,
+    mut message_queues: Query<&mut ImmediateOutMessageQueue>,
+    mut commands: Commands,
+) {
+    let new_client = trigger.entity();
+
+    let player = match awaiting_clients
+        .iter()
+        .find(|(_, session_token)| ***session_token == trigger.session_token)
+    {
+        Some((player, _)) => player,
+        None => {
+            warn!("Client {:?} sent an unknown session token", new_client);
+            message_queues
+                .get_mut(new_client)
+                .expect("Failed to get client message queue")
+                .push_back(MessageContainer::new(
+                    MessageTarget::Client(new_client),
+                    NetworkMessageType::MessageError(ErrorMessageTypes::InvalidSessionToken(
+                        "No disconnected player with this session token".to_string(),
+                    )),
+                ));
+
+            return;
+        }
+    };
+
+    // The player keeps its entity, tank, team and spawn point, only the connection is replaced
+    match clients.get_many_mut([new_client, player]) {
+        Ok([mut new_client_connection, mut player_connection]) => std::mem::swap(
+            &mut new_client_connection.connection,
+            &mut player_connection.connection,
+        ),
+        Err(e) => {
+            error!("Failed to get reconnecting clients: {}", e);
+            return;
+        }
+    }
+    info!("Client {:?} reconnected as {:?}", new_client, player);
+
+    let negotiated_protocol = trigger.negotiated_protocol.clone();
+    let encoding = if negotiated_protocol.has_capability(&ProtocolCapability::BinaryEncoding) {
+        MessageEncoding::MessagePack
+    } else {
+        MessageEncoding::Json
+    };
+
+    let mut message_queue = message_queues
+        .get_mut(player)
+        .expect("Failed to get client message queue");
+    message_queue.push_back(MessageContainer::new(
+        MessageTarget::Client(player),
+        NetworkMessageType::ProtocolNegotiated(negotiated_protocol.clone()),
+    ));
+    message_queue.push_back(MessageContainer::new(
+        MessageTarget::Client(player),
+        NetworkMessageType::SessionToken(SessionToken {
+            token: trigger.session_token.clone(),
+        }),
+    ));
+
+    commands
+        .entity(player)
+        .remove::<AwaitingReconnect>()
+        .insert((negotiated_protocol, encoding));
+    commands.entity(new_client).despawn_recursive();
+    commands.trigger_targets(ClientReconnectedTrigger, player);
+}
+
+/// Sends the game configuration to a reconnected player again, it most likely lost it with its old connection
+/// No game state was acknowledged since the player disconnected, so the next one is sent in full
+pub fn send_game_starts_on_reconnect(
+    trigger: Trigger<ClientReconnectedTrigger>,
+    clients: Query<(&MyNetworkClient, &ClientType, Option<&TankType>)>,
+    in_lobby: Query<&InLobby>,
+    mut message_queues: Query<&mut ImmediateOutMessageQueue>,
+    lobby_management: LobbyManagementSystemParam,
+    server_config: ServerConfigSystemParam,
+    tank_config: TankConfigSystemParam,
+) {
+    let player = trigger.entity();
+    let lobby_entity = **in_lobby.get(player).expect("Failed to get lobby of client");
+    let lobby = lobby_management
+        .get_lobby(lobby_entity)
+        .expect("Failed to get lobby");
+    let connected_clients =
+        get_connected_configs_in_lobby(&lobby_management, lobby_entity, &clients);
+
+    message_queues
+        .get_mut(player)
+        .expect("Failed to get client message queue")
+        .push_back(MessageContainer::new(
+            MessageTarget::Client(player),
+            NetworkMessageType::GameStarts(game_starts_for_client(
+                player,
+                lobby,
+                connected_clients,
+                server_config.server_config(),
+                tank_config.tank_configs(),
+            )),
+        ));
+}
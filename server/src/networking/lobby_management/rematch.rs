@@ -0,0 +1,180 @@
+use bevy::prelude::*;
+use shared::{
+    asset_handling::maps::{MapConfig, MapConfigSystemParam},
+    game::game_state::{LobbyGameState, PersonalizedClientGameState},
+    networking::{
+        lobby_management::{
+            lobby_management::LobbyManagementSystemParam, LobbyRosterChangedTrigger, LobbyState,
+        },
+        messages::{
+            message_container::RematchTrigger,
+            message_data::{first_contact::ClientType, message_error_types::ErrorMessageTypes},
+            message_queue::ImmediateOutMessageQueue,
+        },
+    },
+};
+
+use crate::{
+    gameplay::delta_game_states::SentGameStates, networking::handle_clients::lib::MyNetworkClient,
+};
+
+use super::{lobby_host::check_is_host, lobby_membership::send_error};
+
+/// Resets a finished lobby, so the same members can start another game
+pub fn handle_rematch(
+    trigger: Trigger<RematchTrigger>,
+    mut lobby_management: LobbyManagementSystemParam,
+    map_configs: MapConfigSystemParam,
+    mut clients: Query<(
+        &mut MyNetworkClient,
+        &mut PersonalizedClientGameState,
+        &mut SentGameStates,
+    )>,
+    mut message_queues: Query<&mut ImmediateOutMessageQueue>,
+    mut commands: Commands,
+) {
+    let lobby_entity = trigger.entity();
+    let sender = trigger.sender.expect("Message sender not found");
+
+    if let Err(e) = check_is_host(&lobby_management, lobby_entity, sender)
+        .and_then(|_| check_lobby_finished(&lobby_management, lobby_entity))
+    {
+        send_error(&mut message_queues, sender, e);
+        return;
+    }
+
+    let lobby = lobby_management
+        .get_lobby(lobby_entity)
+        .expect("Failed to get lobby");
+    // Dummies are added again when the game starts
+    let dummies = lobby
+        .players
+        .iter()
+        .filter(|(_, _, client_type)| *client_type == ClientType::Dummy)
+        .map(|(_, dummy, _)| *dummy)
+        .collect::<Vec<_>>();
+    let mut map_name = lobby.map_name.clone();
+    let mut map_config = lobby
+        .map_config
+        .clone()
+        .expect("Map config should be set up by now");
+    for dummy in dummies.iter() {
+        map_config.remove_player_from_team(*dummy);
+    }
+
+    if trigger.next_map {
+        match next_map(&map_configs, &map_name, &map_config) {
+            Ok((next_map_name, next_map_config)) => {
+                map_name = next_map_name;
+                map_config = next_map_config;
+            }
+            Err(e) => {
+                send_error(&mut message_queues, sender, e);
+                return;
+            }
+        }
+    }
+    if trigger.swap_sides {
+        map_config.swap_sides();
+    }
+
+    let mut lobby = lobby_management
+        .get_lobby_mut(lobby_entity)
+        .expect("Failed to get lobby");
+    info!(
+        "Rematch in lobby \"{}\" on map \"{}\"",
+        lobby.lobby_name, map_name
+    );
+
+    for entity in dummies
+        .iter()
+        .chain(lobby.projectiles.iter())
+        .chain(lobby.mines.iter())
+    {
+        commands.entity(*entity).despawn_recursive();
+    }
+    lobby
+        .players
+        .retain(|(_, _, client_type)| *client_type != ClientType::Dummy);
+    lobby.projectiles.clear();
+    lobby.mines.clear();
+
+    lobby.map_name = map_name;
+    lobby.map_config = Some(map_config);
+    lobby.ready_players.clear();
+    lobby.tick_processed = 0;
+    lobby.tick_timer.reset();
+    lobby.state = LobbyState::ReadyToStart;
+
+    let members = lobby
+        .players
+        .iter()
+        .map(|(_, player, _)| *player)
+        .chain(lobby.spectators.iter().cloned())
+        .collect::<Vec<_>>();
+    for member in members {
+        if let Ok((mut client, mut client_state, mut sent_game_states)) = clients.get_mut(member) {
+            *client_state = PersonalizedClientGameState::default();
+            sent_game_states.reset();
+            // The spawn points are assigned again when the game starts
+            if trigger.swap_sides || trigger.next_map {
+                client.assigned_spawn_point = None;
+            }
+        }
+    }
+
+    *lobby_management
+        .get_lobby_gamestate_mut(lobby_entity)
+        .expect("Failed to get lobby game state") = LobbyGameState::default();
+
+    commands.trigger_targets(LobbyRosterChangedTrigger, lobby_entity);
+}
+
+fn check_lobby_finished(
+    lobby_management: &LobbyManagementSystemParam,
+    lobby_entity: Entity,
+) -> Result<(), ErrorMessageTypes> {
+    let lobby = lobby_management
+        .get_lobby(lobby_entity)
+        .expect("Failed to get lobby");
+
+    if lobby.state == LobbyState::Finished {
+        Ok(())
+    } else {
+        Err(ErrorMessageTypes::LobbyManagementError(format!(
+            "A rematch can only be started once the game finished, the lobby is in state {:?}",
+            lobby.state
+        )))
+    }
+}
+
+/// The next map after the current one (sorted by name) that has room for all teams
+fn next_map(
+    map_configs: &MapConfigSystemParam,
+    current_map_name: &str,
+    current_map_config: &MapConfig,
+) -> Result<(String, MapConfig), ErrorMessageTypes> {
+    let mut map_names = map_configs.list_map_names();
+    map_names.sort();
+    let start = map_names
+        .iter()
+        .position(|map_name| map_name == current_map_name)
+        .map_or(0, |index| index + 1);
+
+    map_names
+        .iter()
+        .cycle()
+        .skip(start)
+        .take(map_names.len())
+        .filter(|map_name| *map_name != current_map_name)
+        .find_map(|map_name| {
+            map_configs
+                .get_map_config_from_name(map_name)
+                .and_then(|map_config| map_config.with_players_of(current_map_config).ok())
+                .map(|map_config| (map_name.clone(), map_config))
+        })
+        .ok_or(ErrorMessageTypes::LobbyManagementError(format!(
+            "No other map than \"{}\" has room for all teams",
+            current_map_name
+        )))
+}
@@ -1,19 +1,21 @@
 use bevy::prelude::*;
 use handle_clients::HandleClientsPlugin;
 use handle_messages::HandleMessagesPlugin;
-use lib::MyTcpListener;
 use lobby_management::MyLobbyManagementPlugin;
+use networking_thread::{spawn_networking_thread, NetworkListeners};
 use shared::{
     asset_handling::config::ServerConfigSystemParam,
     main_state::MyMainState,
     networking::{networking_state::MyNetworkingState, networking_system_sets::MyNetworkingSet},
 };
-use std::net::TcpListener;
+use std::{net::TcpListener, time::Duration};
 
 pub mod handle_clients;
 pub mod handle_messages;
 pub mod lib;
 pub mod lobby_management;
+pub mod networking_thread;
+pub mod transport;
 
 use crate::gameplay::system_sets::MyGameplaySet;
 
@@ -53,6 +55,33 @@ fn setup_listener(
         .set_nonblocking(true)
         .expect("Cannot set non-blocking mode");
 
-    commands.insert_resource(MyTcpListener { listener });
+    let websocket_listener = config.websocket_port.map(|websocket_port| {
+        let websocket_listener = TcpListener::bind(format!("{:}:{:}", config.ip, websocket_port))
+            .unwrap_or_else(|e| {
+                panic!(
+                    "Failed to bind to WebSocket port {} on {}: {}",
+                    websocket_port, config.ip, e
+                )
+            });
+        info!(
+            "WebSocket server listening on {}",
+            websocket_listener.local_addr().unwrap()
+        );
+
+        websocket_listener
+            .set_nonblocking(true)
+            .expect("Cannot set non-blocking mode");
+
+        websocket_listener
+    });
+
+    // All socket I/O happens on its own thread, the systems only exchange messages with it
+    commands.insert_resource(spawn_networking_thread(NetworkListeners::new(
+        listener,
+        websocket_listener,
+        config.max_frame_size,
+        // WebSocket clients have to finish their handshake in the time they have for their first contact
+        Duration::from_millis(config.timeout_first_contact),
+    )));
     networking_state.set(MyNetworkingState::Running);
 }
@@ -0,0 +1,398 @@
+use std::{
+    collections::VecDeque,
+    io,
+    net::{TcpListener, TcpStream},
+    thread,
+    time::{Duration, Instant},
+};
+
+use bevy::prelude::*;
+use crossbeam_channel::{Receiver, Sender, TryRecvError, TrySendError};
+use shared::networking::{
+    framing::FrameTooLarge, message_codec::MessageEncoding,
+    messages::message_container::MessageContainer,
+};
+use tungstenite::{
+    handshake::{server::NoCallback, MidHandshake},
+    protocol::WebSocketConfig,
+    HandshakeError, ServerHandshake,
+};
+
+use super::{
+    lib::MyNetworkingThread,
+    transport::{tcp::TcpTransport, websocket::WebSocketTransport, Transport},
+};
+
+/// How many batches of messages can be queued for a single client, in each direction
+/// A client that does not read its messages fast enough to stay below this gets disconnected
+pub const MAX_QUEUED_BATCHES: usize = 64;
+
+/// How many WebSocket handshakes can be in progress at once, further connections are closed right away
+const MAX_PENDING_HANDSHAKES: usize = 64;
+
+/// How long the networking thread sleeps if there was nothing to do
+const IDLE_SLEEP: Duration = Duration::from_millis(1);
+
+/// A batch of messages to be sent to a client, encoded by the networking thread
+#[derive(Debug)]
+pub struct OutgoingBatch {
+    pub encoding: MessageEncoding,
+    pub messages: Vec<MessageContainer>,
+}
+
+/// What the networking thread received from a client
+#[derive(Debug)]
+pub enum IncomingBatch {
+    Messages(Vec<MessageContainer>),
+    /// The client sent a frame exceeding the maximum frame size, it was discarded
+    FrameTooLarge(String),
+}
+
+/// The ECS side of a connection, the transport itself is owned by the networking thread
+/// Dropping it closes the connection
+#[derive(Debug)]
+pub struct ClientConnection {
+    address: Option<String>,
+    incoming: Receiver<IncomingBatch>,
+    outgoing: Sender<OutgoingBatch>,
+}
+
+impl ClientConnection {
+    pub fn address(&self) -> Option<String> {
+        self.address.clone()
+    }
+
+    /// Returns the next batch received from the client, never blocks
+    /// Returns an error once the connection was closed and all received messages were handled
+    pub fn try_receive(&self) -> Result<Option<IncomingBatch>, String> {
+        match self.incoming.try_recv() {
+            Ok(batch) => Ok(Some(batch)),
+            Err(TryRecvError::Empty) => Ok(None),
+            Err(TryRecvError::Disconnected) => Err("Connection closed".to_string()),
+        }
+    }
+
+    /// Queues the messages to be sent to the client, never blocks
+    /// Returns an error if the connection was closed or the client can't keep up with reading its messages
+    pub fn send(
+        &self,
+        encoding: MessageEncoding,
+        messages: Vec<MessageContainer>,
+    ) -> Result<(), String> {
+        match self.outgoing.try_send(OutgoingBatch { encoding, messages }) {
+            Ok(()) => Ok(()),
+            Err(TrySendError::Full(_)) => Err(format!(
+                "Client is too slow, more than {} batches of messages are waiting to be sent",
+                MAX_QUEUED_BATCHES
+            )),
+            Err(TrySendError::Disconnected(_)) => Err("Connection closed".to_string()),
+        }
+    }
+}
+
+/// The networking thread's side of a connection
+pub struct ConnectionWorker {
+    transport: Box<dyn Transport>,
+    incoming: Sender<IncomingBatch>,
+    outgoing: Receiver<OutgoingBatch>,
+    /// Decoded batches that did not fit into the incoming channel yet
+    pending_incoming: VecDeque<IncomingBatch>,
+}
+
+/// Creates both sides of a connection using the transport
+pub fn connect(transport: Box<dyn Transport>) -> (ClientConnection, ConnectionWorker) {
+    let (incoming_sender, incoming_receiver) = crossbeam_channel::bounded(MAX_QUEUED_BATCHES);
+    let (outgoing_sender, outgoing_receiver) = crossbeam_channel::bounded(MAX_QUEUED_BATCHES);
+
+    (
+        ClientConnection {
+            address: transport.peer_addr(),
+            incoming: incoming_receiver,
+            outgoing: outgoing_sender,
+        },
+        ConnectionWorker {
+            transport,
+            incoming: incoming_sender,
+            outgoing: outgoing_receiver,
+            pending_incoming: VecDeque::new(),
+        },
+    )
+}
+
+impl ConnectionWorker {
+    /// Reads, decodes, encodes and writes whatever is possible without blocking
+    /// Returns whether anything was done, or an error if the connection should be closed
+    fn update(&mut self) -> Result<bool, String> {
+        let mut did_work = self.forward_incoming()?;
+
+        // Only read more once the server handled the earlier messages, a client flooding us has to wait
+        if self.pending_incoming.is_empty() {
+            self.transport.receive().map_err(|e| e.to_string())?;
+
+            loop {
+                let payload = match self.transport.next_payload() {
+                    Ok(Some(payload)) => payload,
+                    Ok(None) => break,
+                    // The server decides what happens to a client sending too large frames
+                    Err(e) => match FrameTooLarge::from_io_error(&e) {
+                        Some(frame_too_large) => {
+                            did_work = true;
+                            self.pending_incoming
+                                .push_back(IncomingBatch::FrameTooLarge(
+                                    frame_too_large.to_string(),
+                                ));
+                            continue;
+                        }
+                        None => return Err(e.to_string()),
+                    },
+                };
+                did_work = true;
+
+                // Decode the payload into an array of MessageContainers, in whatever encoding the client used
+                let encoding = MessageEncoding::detect(&payload);
+                match encoding.codec().decode(&payload) {
+                    Ok(messages) => self
+                        .pending_incoming
+                        .push_back(IncomingBatch::Messages(messages)),
+                    Err(e) => {
+                        error!(
+                            "Failed to decode {:?} array from {:?}: {}. Raw data: {}",
+                            encoding,
+                            self.transport.peer_addr(),
+                            e,
+                            String::from_utf8_lossy(&payload)
+                        );
+                        // TODO add error message to queue
+                    }
+                }
+            }
+
+            did_work |= self.forward_incoming()?;
+        }
+
+        // Only hand new payloads to the transport once it wrote everything from before,
+        // so a slow client fills up its own outgoing channel instead of our memory
+        self.transport.flush().map_err(|e| e.to_string())?;
+        while !self.transport.has_pending_writes() {
+            let batch = match self.outgoing.try_recv() {
+                Ok(batch) => batch,
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return Err("Closed by the server".to_string()),
+            };
+            did_work = true;
+
+            match batch.encoding.codec().encode(&batch.messages) {
+                Ok(payload) => self
+                    .transport
+                    .send(batch.encoding, &payload)
+                    .map_err(|e| e.to_string())?,
+                Err(e) => error!("Failed to serialize messages: {}", e),
+            }
+        }
+
+        Ok(did_work)
+    }
+
+    /// Hands the decoded batches to the server, as far as the incoming channel has room
+    fn forward_incoming(&mut self) -> Result<bool, String> {
+        let mut did_work = false;
+
+        while let Some(batch) = self.pending_incoming.pop_front() {
+            match self.incoming.try_send(batch) {
+                Ok(()) => did_work = true,
+                Err(TrySendError::Full(batch)) => {
+                    self.pending_incoming.push_front(batch);
+                    break;
+                }
+                Err(TrySendError::Disconnected(_)) => {
+                    return Err("Closed by the server".to_string())
+                }
+            }
+        }
+
+        Ok(did_work)
+    }
+}
+
+/// The listeners the networking thread accepts new connections on
+pub struct NetworkListeners {
+    pub tcp: TcpListener,
+    /// Only set up if a WebSocket port is configured
+    pub websocket: Option<TcpListener>,
+    /// The maximum size of a payload received on any of the accepted connections
+    max_frame_size: u32,
+    /// How long a WebSocket handshake can take before the connection is closed
+    handshake_timeout: Duration,
+    /// Connections whose WebSocket handshake did not finish yet, with the time it started
+    pending_handshakes: Vec<(
+        Instant,
+        MidHandshake<ServerHandshake<TcpStream, NoCallback>>,
+    )>,
+}
+
+impl NetworkListeners {
+    /// Expects the listeners to be non-blocking
+    pub fn new(
+        tcp: TcpListener,
+        websocket: Option<TcpListener>,
+        max_frame_size: u32,
+        handshake_timeout: Duration,
+    ) -> Self {
+        Self {
+            tcp,
+            websocket,
+            max_frame_size,
+            handshake_timeout,
+            pending_handshakes: Vec::new(),
+        }
+    }
+
+    /// Returns the transports of all connections that were accepted since the last call
+    fn accept(&mut self) -> Vec<Box<dyn Transport>> {
+        let mut transports: Vec<Box<dyn Transport>> = Vec::new();
+
+        for stream in accept_streams(&self.tcp) {
+            transports.push(Box::new(TcpTransport::new(stream, self.max_frame_size)));
+        }
+
+        // Handshakes might not finish at once on a non-blocking stream, so we continue them in the next update
+        // The ones started earlier come first, so new connections can't push them out
+        let mut handshake_results = Vec::new();
+        for (started, pending_handshake) in self.pending_handshakes.drain(..) {
+            handshake_results.push((started, pending_handshake.handshake()));
+        }
+
+        if let Some(websocket) = &self.websocket {
+            // tungstenite can't skip a too large message, so WebSocket clients exceeding the limit get disconnected
+            let config = WebSocketConfig::default()
+                .max_message_size(Some(self.max_frame_size as usize))
+                .max_frame_size(Some(self.max_frame_size as usize));
+            for stream in accept_streams(websocket) {
+                handshake_results.push((
+                    Instant::now(),
+                    tungstenite::accept_with_config(stream, Some(config)),
+                ));
+            }
+        }
+
+        for (started, handshake_result) in handshake_results {
+            match handshake_result {
+                Ok(socket) => transports.push(Box::new(WebSocketTransport::new(socket))),
+                // Dropping the handshake closes the connection
+                Err(HandshakeError::Interrupted(_))
+                    if started.elapsed() > self.handshake_timeout =>
+                {
+                    warn!("WebSocket handshake timed out");
+                }
+                Err(HandshakeError::Interrupted(_))
+                    if self.pending_handshakes.len() >= MAX_PENDING_HANDSHAKES =>
+                {
+                    warn!(
+                        "More than {} WebSocket handshakes are pending, closing the connection",
+                        MAX_PENDING_HANDSHAKES
+                    );
+                }
+                Err(HandshakeError::Interrupted(pending_handshake)) => {
+                    self.pending_handshakes.push((started, pending_handshake));
+                }
+                Err(HandshakeError::Failure(e)) => {
+                    error!("WebSocket handshake failed: {}", e);
+                }
+            }
+        }
+
+        transports
+    }
+}
+
+/// Accepts in a loop until we get a WouldBlock error
+fn accept_streams(listener: &TcpListener) -> Vec<TcpStream> {
+    let mut streams = Vec::new();
+
+    loop {
+        match listener.accept() {
+            Ok((stream, _addr)) => match stream.set_nonblocking(true) {
+                Ok(()) => streams.push(stream),
+                Err(e) => error!("Failed to set non-blocking mode: {}", e),
+            },
+            Err(e) => {
+                if e.kind() != io::ErrorKind::WouldBlock {
+                    // Some other error, e.g. connection reset, etc.
+                    error!("Accept error: {}", e);
+                }
+                break;
+            }
+        }
+    }
+
+    streams
+}
+
+/// Spawns the thread that does all the socket I/O, so a slow client never stalls the simulation of the lobbies
+pub fn spawn_networking_thread(listeners: NetworkListeners) -> MyNetworkingThread {
+    let (new_connections_sender, new_connections_receiver) = crossbeam_channel::unbounded();
+    let (workers_sender, workers_receiver) = crossbeam_channel::unbounded();
+
+    thread::Builder::new()
+        .name("networking".to_string())
+        .spawn(move || run_networking_thread(listeners, new_connections_sender, workers_receiver))
+        .expect("Failed to spawn networking thread");
+
+    MyNetworkingThread {
+        new_connections: new_connections_receiver,
+        workers: workers_sender,
+    }
+}
+
+fn run_networking_thread(
+    mut listeners: NetworkListeners,
+    new_connections: Sender<ClientConnection>,
+    new_workers: Receiver<ConnectionWorker>,
+) {
+    let mut workers: Vec<ConnectionWorker> = Vec::new();
+
+    loop {
+        let mut did_work = false;
+
+        for transport in listeners.accept() {
+            let (connection, worker) = connect(transport);
+            if new_connections.send(connection).is_err() {
+                // The server shut down
+                return;
+            }
+            workers.push(worker);
+            did_work = true;
+        }
+
+        // Connections created by the server itself, e.g. for dummies
+        loop {
+            match new_workers.try_recv() {
+                Ok(worker) => {
+                    workers.push(worker);
+                    did_work = true;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => return,
+            }
+        }
+
+        workers.retain_mut(|worker| match worker.update() {
+            Ok(worked) => {
+                did_work |= worked;
+                true
+            }
+            Err(e) => {
+                info!(
+                    "Closing connection to {:?}: {}",
+                    worker.transport.peer_addr(),
+                    e
+                );
+                false
+            }
+        });
+
+        if !did_work {
+            thread::sleep(IDLE_SLEEP);
+        }
+    }
+}
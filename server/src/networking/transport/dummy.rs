@@ -0,0 +1,35 @@
+use std::io;
+
+use shared::networking::message_codec::MessageEncoding;
+
+use super::Transport;
+
+/// The transport of dummy clients, never receives anything and drops everything sent to it
+#[derive(Debug, Default)]
+pub struct DummyTransport;
+
+impl Transport for DummyTransport {
+    fn peer_addr(&self) -> Option<String> {
+        None
+    }
+
+    fn receive(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn next_payload(&mut self) -> io::Result<Option<Vec<u8>>> {
+        Ok(None)
+    }
+
+    fn send(&mut self, _encoding: MessageEncoding, _payload: &[u8]) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn has_pending_writes(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,107 @@
+use std::{
+    collections::VecDeque,
+    io,
+    sync::{Arc, Mutex},
+};
+
+use shared::networking::message_codec::MessageEncoding;
+
+use super::Transport;
+
+/// A queue of payloads shared by the two ends of an in-memory connection
+type SharedQueue = Arc<Mutex<VecDeque<Vec<u8>>>>;
+
+/// A connection that never leaves the process, e.g. for bots running inside the server or test harnesses
+/// Always created as a connected pair, see `InMemoryTransport::pair`
+#[derive(Debug)]
+pub struct InMemoryTransport {
+    incoming: SharedQueue,
+    outgoing: SharedQueue,
+    /// Payloads received but not yet handled
+    received: VecDeque<Vec<u8>>,
+}
+
+impl InMemoryTransport {
+    /// Creates two connected transports, everything sent on one of them is received by the other
+    pub fn pair() -> (Self, Self) {
+        let a_to_b = SharedQueue::default();
+        let b_to_a = SharedQueue::default();
+
+        (
+            Self {
+                incoming: b_to_a.clone(),
+                outgoing: a_to_b.clone(),
+                received: VecDeque::new(),
+            },
+            Self {
+                incoming: a_to_b,
+                outgoing: b_to_a,
+                received: VecDeque::new(),
+            },
+        )
+    }
+
+    /// Whether the other end of the connection was dropped
+    pub fn is_closed(&self) -> bool {
+        Arc::strong_count(&self.outgoing) == 1
+    }
+}
+
+impl Transport for InMemoryTransport {
+    fn peer_addr(&self) -> Option<String> {
+        None
+    }
+
+    fn receive(&mut self) -> io::Result<()> {
+        self.received.extend(
+            self.incoming
+                .lock()
+                .expect("Failed to lock in-memory queue")
+                .drain(..),
+        );
+
+        // Payloads sent before the other end was dropped are still handled
+        if self.received.is_empty() && self.is_closed() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "In-memory connection closed by peer",
+            ));
+        }
+
+        Ok(())
+    }
+
+    fn next_payload(&mut self) -> io::Result<Option<Vec<u8>>> {
+        while let Some(payload) = self.received.pop_front() {
+            if !payload.is_empty() {
+                return Ok(Some(payload));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn send(&mut self, _encoding: MessageEncoding, payload: &[u8]) -> io::Result<()> {
+        if self.is_closed() {
+            return Err(io::Error::new(
+                io::ErrorKind::BrokenPipe,
+                "In-memory connection closed by peer",
+            ));
+        }
+
+        self.outgoing
+            .lock()
+            .expect("Failed to lock in-memory queue")
+            .push_back(payload.to_vec());
+
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn has_pending_writes(&self) -> bool {
+        false
+    }
+}
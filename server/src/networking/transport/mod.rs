@@ -0,0 +1,34 @@
+use std::{fmt::Debug, io};
+
+use shared::networking::message_codec::MessageEncoding;
+
+pub mod dummy;
+pub mod in_memory;
+pub mod tcp;
+pub mod websocket;
+
+/// The connection to a client, all reading and writing of the server goes through this trait
+/// The game logic does not care which transport a client uses
+///
+/// All methods are called from the networking thread, which serves all clients, so they must never block
+pub trait Transport: Debug + Send + Sync {
+    /// The address of the other side, if there is one
+    fn peer_addr(&self) -> Option<String>;
+
+    /// Reads everything that is currently available
+    /// Returns an error if the connection was closed or broke
+    fn receive(&mut self) -> io::Result<()>;
+
+    /// Returns the next complete, non-empty payload that was received
+    fn next_payload(&mut self) -> io::Result<Option<Vec<u8>>>;
+
+    /// Sends one payload (an encoded array of MessageContainers) to the other side
+    /// Whatever can't be written right away is buffered and written by the following calls to `flush`
+    fn send(&mut self, encoding: MessageEncoding, payload: &[u8]) -> io::Result<()>;
+
+    /// Writes as much of the buffered data as possible
+    fn flush(&mut self) -> io::Result<()>;
+
+    /// Whether there is buffered data that could not be written yet
+    fn has_pending_writes(&self) -> bool;
+}
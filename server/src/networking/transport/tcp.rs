@@ -0,0 +1,74 @@
+use std::{
+    io::{self, Write},
+    net::TcpStream,
+};
+
+use shared::networking::{framing::FrameCodec, message_codec::MessageEncoding};
+
+use super::Transport;
+
+/// A raw TCP stream, using the length prefixed frames of the `FrameCodec`
+#[derive(Debug)]
+pub struct TcpTransport {
+    stream: TcpStream,
+    codec: FrameCodec,
+    /// Encoded frames that were not completely written yet
+    send_buffer: Vec<u8>,
+}
+
+impl TcpTransport {
+    /// Expects the stream to be non-blocking
+    /// Received frames larger than `max_frame_size` are discarded
+    pub fn new(stream: TcpStream, max_frame_size: u32) -> Self {
+        Self {
+            stream,
+            codec: FrameCodec::with_max_frame_size(max_frame_size),
+            send_buffer: Vec::new(),
+        }
+    }
+}
+
+impl Transport for TcpTransport {
+    fn peer_addr(&self) -> Option<String> {
+        self.stream.peer_addr().ok().map(|addr| addr.to_string())
+    }
+
+    fn receive(&mut self) -> io::Result<()> {
+        self.codec.receive(&mut self.stream)
+    }
+
+    fn next_payload(&mut self) -> io::Result<Option<Vec<u8>>> {
+        self.codec.next_frame()
+    }
+
+    fn send(&mut self, _encoding: MessageEncoding, payload: &[u8]) -> io::Result<()> {
+        let frame = self.codec.encode_frame(payload);
+        self.send_buffer.extend_from_slice(&frame);
+        self.flush()
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        while !self.send_buffer.is_empty() {
+            match self.stream.write(&self.send_buffer) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::WriteZero,
+                        "Connection closed by peer",
+                    ))
+                }
+                Ok(written) => {
+                    self.send_buffer.drain(..written);
+                }
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(())
+    }
+
+    fn has_pending_writes(&self) -> bool {
+        !self.send_buffer.is_empty()
+    }
+}
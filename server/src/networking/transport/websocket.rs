@@ -0,0 +1,111 @@
+use std::{collections::VecDeque, io, net::TcpStream};
+
+use shared::networking::message_codec::MessageEncoding;
+use tungstenite::{Message, WebSocket};
+
+use super::Transport;
+
+/// A WebSocket, each WebSocket message carries one payload
+#[derive(Debug)]
+pub struct WebSocketTransport {
+    socket: WebSocket<TcpStream>,
+    /// Payloads received but not yet handled
+    received: VecDeque<Vec<u8>>,
+    /// Whether the last flush could not write everything buffered by tungstenite
+    pending_writes: bool,
+}
+
+impl WebSocketTransport {
+    /// Expects the underlying stream to be non-blocking
+    pub fn new(socket: WebSocket<TcpStream>) -> Self {
+        Self {
+            socket,
+            received: VecDeque::new(),
+            pending_writes: false,
+        }
+    }
+}
+
+impl Transport for WebSocketTransport {
+    fn peer_addr(&self) -> Option<String> {
+        self.socket
+            .get_ref()
+            .peer_addr()
+            .ok()
+            .map(|addr| addr.to_string())
+    }
+
+    fn receive(&mut self) -> io::Result<()> {
+        loop {
+            match self.socket.read() {
+                Ok(Message::Text(text)) => self.received.push_back(text.as_bytes().to_vec()),
+                Ok(Message::Binary(data)) => self.received.push_back(data.to_vec()),
+                Ok(Message::Close(_)) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "WebSocket closed by peer",
+                    ))
+                }
+                // Pings are answered by tungstenite itself
+                Ok(_) => {}
+                Err(tungstenite::Error::Io(e)) if e.kind() == io::ErrorKind::WouldBlock => {
+                    return Ok(())
+                }
+                Err(e) => return Err(into_io_error(e)),
+            }
+        }
+    }
+
+    fn next_payload(&mut self) -> io::Result<Option<Vec<u8>>> {
+        while let Some(payload) = self.received.pop_front() {
+            if !payload.is_empty() {
+                return Ok(Some(payload));
+            }
+        }
+
+        Ok(None)
+    }
+
+    fn send(&mut self, encoding: MessageEncoding, payload: &[u8]) -> io::Result<()> {
+        let message = match encoding {
+            MessageEncoding::Json => Message::text(String::from_utf8_lossy(payload).into_owned()),
+            MessageEncoding::MessagePack => Message::binary(payload.to_vec()),
+        };
+
+        match self.socket.write(message) {
+            Ok(()) => self.flush(),
+            // The message was queued, tungstenite only could not write out its buffer yet
+            Err(tungstenite::Error::Io(e)) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.pending_writes = true;
+                Ok(())
+            }
+            Err(e) => Err(into_io_error(e)),
+        }
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.socket.flush() {
+            Ok(()) => {
+                self.pending_writes = false;
+                Ok(())
+            }
+            // The rest stays buffered by tungstenite until the next flush
+            Err(tungstenite::Error::Io(e)) if e.kind() == io::ErrorKind::WouldBlock => {
+                self.pending_writes = true;
+                Ok(())
+            }
+            Err(e) => Err(into_io_error(e)),
+        }
+    }
+
+    fn has_pending_writes(&self) -> bool {
+        self.pending_writes
+    }
+}
+
+fn into_io_error(error: tungstenite::Error) -> io::Error {
+    match error {
+        tungstenite::Error::Io(e) => e,
+        e => io::Error::other(e),
+    }
+}
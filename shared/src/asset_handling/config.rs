@@ -25,6 +25,7 @@ impl Plugin for MyConfigPlugin {
         .register_type::<ClientConfig>()
         .register_type::<TankConfigs>()
         .register_type::<TankConfig>()
+        .register_type::<ModuleCriticalConfig>()
         .configure_loading_state(
             LoadingStateConfig::new(MyMainState::SettingUp).load_collection::<MyConfigAsset>(),
         );
@@ -48,6 +49,22 @@ pub struct ServerConfig {
     pub port: u16,
     pub tick_rate: u64,
     pub timeout_first_contact: u64, // in milliseconds
+    /// How long a player that lost its connection during a match can reconnect, before it is removed from the lobby
+    pub reconnect_grace_period: u64, // in milliseconds
+    /// The port to listen on for WebSocket connections, None disables WebSockets
+    #[serde(default)]
+    pub websocket_port: Option<u16>,
+    /// The maximum size of a received payload in bytes, larger frames are discarded
+    pub max_frame_size: u32,
+    /// The maximum number of messages a client can send per tick
+    pub max_messages_per_tick: usize,
+    /// The maximum number of commands of each type (e.g. `MoveTankCommand`) a client can send per tick
+    pub max_commands_per_tick: usize,
+    /// How often a client can break one of the limits above before it gets disconnected
+    pub max_violations: u32,
+    /// Clients sending this password in their first contact become host of the lobby they join, None disables admins
+    #[serde(default)]
+    pub admin_password: Option<String>,
 }
 
 #[derive(Debug, Default, Reflect, Clone, Asset, Deserialize)]
@@ -58,6 +75,12 @@ pub struct ClientConfig {
     pub name: String,
     pub lobby_name: String,
     pub fill_empty_slots_with_dummies: bool,
+    /// The password of the lobby to watch, if it has one
+    #[serde(default)]
+    pub lobby_password: Option<String>,
+    /// The admin password of the server, to become host of the watched lobby
+    #[serde(default)]
+    pub admin_password: Option<String>,
 }
 
 #[derive(Debug, Default, Reflect, Clone, Asset, Deserialize, PartialEq)]
@@ -70,6 +93,18 @@ pub struct TankConfigs {
 pub struct TankConfig {
     /// The speed at which the tank at maximum moves per tick
     pub move_speed: f32,
+    /// The speed at which the tank at maximum moves backwards per tick.
+    /// None means the tank moves backwards as fast as forwards
+    #[serde(default)]
+    pub max_reverse_speed: Option<f32>,
+    /// How much the speed of the tank increases per tick when accelerating.
+    /// None means the tank has no momentum and moves exactly as far as commanded each tick
+    #[serde(default)]
+    pub acceleration: Option<f32>,
+    /// How much the speed of the tank decreases per tick when braking.
+    /// None means the tank brakes as fast as it accelerates
+    #[serde(default)]
+    pub deceleration: Option<f32>,
     /// The speed at which the body of the tank at maximum rotates per tick in radians
     pub body_rotation_speed: f32,
     /// Yaw rotation speed of the turret in radians per tick
@@ -80,6 +115,22 @@ pub struct TankConfig {
     pub turret_min_pitch: f32,
     /// The maximum height this tank can "climb"
     pub max_slope: f32,
+    /// How much the speed is reduced per unit of slope when driving uphill.
+    /// 0.0 means the slope does not slow the tank down
+    #[serde(default)]
+    pub uphill_speed_penalty: f32,
+    /// How much the speed is increased per unit of slope when driving downhill.
+    /// 0.0 means the slope does not speed the tank up
+    #[serde(default)]
+    pub downhill_speed_bonus: f32,
+    /// The maximum height of a ledge this tank can drive down from.
+    /// Ledges higher than `max_slope` can only be driven down, never up.
+    /// 0.0 means the tank can't drive off ledges higher than `max_slope`
+    #[serde(default)]
+    pub max_drop_height: f32,
+    /// The damage taken per unit of height fallen beyond `max_slope`
+    #[serde(default)]
+    pub fall_damage: f32,
     /// The size of the tank (Vec3, x = width, y = height, z = depth)
     /// full-extents for x (width), z (depth) and y (height)
     pub size: Vec3,
@@ -94,6 +145,70 @@ pub struct TankConfig {
     pub projectile_size: Vec3,
     /// The maximum amount of health this tank can have
     pub max_health: f32,
+    /// The health repaired per tick once the tank has not been hit or fired for `repair_delay` ticks.
+    /// 0.0 means the tank does not repair itself
+    #[serde(default)]
+    pub repair_rate: f32,
+    /// The amount of ticks without being hit or firing before the tank starts repairing itself
+    #[serde(default)]
+    pub repair_delay: u32,
+    /// The health repaired per tick while the tank is repairing (see `RepairCommand`).
+    /// The tank can't move while repairing
+    #[serde(default)]
+    pub active_repair_rate: f32,
+    /// The amount of mines this tank can deploy per life
+    #[serde(default)]
+    pub mine_allowance: u32,
+    /// The damage a mine deployed by this tank deals to the enemy driving over it
+    #[serde(default)]
+    pub mine_damage: f32,
+    /// The distance at which this tank spots enemy mines
+    #[serde(default)]
+    pub mine_detection_range: f32,
+    /// Critical hits on the tracks immobilize the tank
+    #[serde(default)]
+    pub track_critical: ModuleCriticalConfig,
+    /// Critical hits on the turret ring stop the turret from rotating
+    #[serde(default)]
+    pub turret_ring_critical: ModuleCriticalConfig,
+    /// Critical hits on the gun add the disabled ticks to the reload time
+    #[serde(default)]
+    pub gun_critical: ModuleCriticalConfig,
+}
+
+#[derive(Debug, Default, Reflect, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ModuleCriticalConfig {
+    /// The chance (0.0 to 1.0) that a hit on this tank disables the module
+    pub chance: f32,
+    /// For how many ticks the module stays disabled
+    pub disabled_ticks: u32,
+}
+
+impl TankConfig {
+    /// Clamps the given speed per tick to the maximum forward and reverse speed of this tank
+    pub fn clamp_speed(&self, speed: f32) -> f32 {
+        speed.clamp(
+            -self.max_reverse_speed.unwrap_or(self.move_speed),
+            self.move_speed,
+        )
+    }
+
+    /// The factor the movement speed is multiplied with when driving on the given slope.
+    /// A positive slope means uphill, a negative slope means downhill.
+    pub fn slope_speed_multiplier(&self, slope: f32) -> f32 {
+        if slope > 0.0 {
+            (1.0 - self.uphill_speed_penalty * slope).clamp(0.0, 1.0)
+        } else {
+            // Only speed up slightly, even if the drop is steep
+            1.0 + self.downhill_speed_bonus * (-slope).min(1.0)
+        }
+    }
+
+    /// The damage taken when falling down the given height
+    pub fn fall_damage_for_height(&self, height: f32) -> f32 {
+        (height - self.max_slope).max(0.0) * self.fall_damage
+    }
 }
 
 #[derive(SystemParam)]
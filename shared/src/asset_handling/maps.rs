@@ -90,6 +90,32 @@ impl MapConfig {
         }
         None
     }
+
+    /// Moves every team to the markers (spawn points, flags) of the next team, sorted by name
+    /// With two teams, they swap sides
+    pub fn swap_sides(&mut self) {
+        let mut team_names = self.teams.keys().cloned().collect::<Vec<_>>();
+        team_names.sort();
+
+        for marker in self.map.markers.iter_mut() {
+            if let Some(index) = team_names.iter().position(|name| *name == marker.group) {
+                marker.group = team_names[(index + 1) % team_names.len()].clone();
+            }
+        }
+    }
+
+    /// A copy of this map with the players of the other map in the same teams
+    /// Fails if a team doesn't exist in this map or is too small
+    pub fn with_players_of(&self, other: &MapConfig) -> Result<MapConfig, ErrorMessageTypes> {
+        let mut map_config = self.clone();
+        for (team_name, team) in other.teams.iter() {
+            for player in team.players.iter() {
+                map_config.insert_player_into_team(team_name, *player)?;
+            }
+        }
+
+        Ok(map_config)
+    }
 }
 
 #[derive(Debug, Clone, Reflect, Default, Serialize, Deserialize, PartialEq)]
@@ -321,6 +347,28 @@ impl MapDefinition {
     pub fn is_inside_bounds(&self, position: Vec3) -> bool {
         self.get_closest_tile(position).is_some()
     }
+
+    /// Returns the floor height of the tile the given (real world) position is on
+    pub fn get_floor_height_at_position(&self, position: Vec3) -> Option<f32> {
+        if position.x < 0.0 || position.z < 0.0 {
+            return None;
+        }
+
+        self.get_floor_height_of_tile((position.x as usize, position.z as usize))
+    }
+
+    /// Returns the slope (height difference per tile) when moving from the given position one tile into the given direction.
+    /// Positive values mean uphill, negative values mean downhill.
+    /// None if the position or the tile in the given direction is outside of the map
+    pub fn get_slope_in_direction(&self, position: Vec3, direction: Vec3) -> Option<f32> {
+        const CELL_SIZE: f32 = 1.0;
+
+        let direction = Vec3::new(direction.x, 0.0, direction.z).normalize_or_zero();
+        let current_height = self.get_floor_height_at_position(position)?;
+        let next_height = self.get_floor_height_at_position(position + direction * CELL_SIZE)?;
+
+        Some((next_height - current_height) / CELL_SIZE)
+    }
 }
 
 #[derive(Debug, Clone, Reflect, Default, Serialize, Deserialize, PartialEq)]
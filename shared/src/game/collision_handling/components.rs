@@ -6,6 +6,8 @@ use bevy::{ecs::entity::EntityHashSet, prelude::*};
 pub struct Collider {
     pub half_size: Vec3,
     pub max_slope: f32,
+    /// The maximum height of a ledge this collider can move down from
+    pub max_drop: f32,
 }
 
 impl Collider {
@@ -13,8 +15,14 @@ impl Collider {
         Self {
             half_size,
             max_slope,
+            max_drop: 0.0,
         }
     }
+
+    pub fn with_max_drop(mut self, max_drop: f32) -> Self {
+        self.max_drop = max_drop;
+        self
+    }
 }
 
 #[derive(Debug, Component, Reflect, Clone, PartialEq, Eq, Default)]
@@ -7,3 +7,9 @@ pub struct CollidedWithTrigger {
 
 #[derive(Debug, Event, Reflect)]
 pub struct CollidedWithWorldTrigger;
+
+/// Sent to an entity that drove off a ledge higher than its max slope
+#[derive(Debug, Event, Reflect)]
+pub struct FellFromLedgeTrigger {
+    pub height: f32,
+}
@@ -1,9 +1,11 @@
 use bevy::{prelude::*, utils::HashMap};
 use serde::{Deserialize, Serialize};
 
-use crate::networking::messages::message_data::game_state::GameState;
+use crate::networking::messages::message_data::{
+    command_acknowledgement::CommandAcknowledgement, game_state::GameState,
+};
 
-use super::player_handling::PlayerState;
+use super::player_handling::{PlayerState, TankModules};
 
 /// The full game state stored in the lobby
 /// This is the state that is sent to the spectators
@@ -14,6 +16,7 @@ pub struct LobbyGameState {
     pub tick: u64,
     pub client_states: HashMap<Entity, ClientState>,
     pub projectiles: HashMap<Entity, ProjectileState>,
+    pub mines: HashMap<Entity, MineState>,
 }
 
 impl From<LobbyGameState> for GameState {
@@ -26,6 +29,8 @@ impl From<LobbyGameState> for GameState {
                 .map(|(entity, client_state)| (entity, Some(client_state)))
                 .collect(),
             projectile_states: lobby_game_state.projectiles,
+            mine_states: lobby_game_state.mines,
+            command_acknowledgements: Vec::new(),
         }
     }
 }
@@ -40,6 +45,10 @@ pub struct PersonalizedClientGameState {
     pub personal_state: ClientState,
     pub other_client_states: HashMap<Entity, Option<ClientState>>,
     pub projectiles: HashMap<Entity, ProjectileState>,
+    /// The mines the client knows about, its own team's mines and the enemy mines it has spotted
+    pub mines: HashMap<Entity, MineState>,
+    /// The commands of the client that were processed since the last state was sent
+    pub command_acknowledgements: Vec<CommandAcknowledgement>,
 }
 
 impl PersonalizedClientGameState {
@@ -51,6 +60,7 @@ impl PersonalizedClientGameState {
                 .map(|state| state.clear_non_persistent_information());
         }
         self.projectiles.clear();
+        self.mines.clear();
     }
 }
 
@@ -71,6 +81,8 @@ impl From<PersonalizedClientGameState> for GameState {
             tick: personalized_client_game_state.tick,
             client_states,
             projectile_states: personalized_client_game_state.projectiles,
+            mine_states: personalized_client_game_state.mines,
+            command_acknowledgements: personalized_client_game_state.command_acknowledgements,
         }
     }
 }
@@ -93,8 +105,17 @@ pub struct ClientState {
     pub transform_turret: Option<Transform>,
     /// The state of the player (e.g. alive, dead)
     pub state: Option<PlayerState>,
+    /// The current speed of the client per tick, negative when driving backwards
+    /// None if the client that receives this state does not know the speed of the client.
+    pub speed: Option<f32>,
+    /// The current health of the client
+    /// None if the client that receives this state does not know the health of the client.
+    pub health: Option<f32>,
     /// The time in ticks until the client can shoot again
     pub shoot_cooldown: u32,
+    /// The state of the client's tank modules (e.g. broken tracks)
+    /// Only known to the client itself.
+    pub modules: Option<TankModules>,
 }
 
 impl ClientState {
@@ -104,7 +125,10 @@ impl ClientState {
             transform_body: None,
             transform_turret: None,
             state: None,
+            speed: None,
+            health: None,
             shoot_cooldown: 0,
+            modules: None,
         }
     }
 
@@ -115,6 +139,9 @@ impl ClientState {
         self.transform_body = None;
         self.transform_turret = None;
         self.state = None;
+        self.speed = None;
+        self.health = None;
+        self.modules = None;
     }
 }
 
@@ -125,7 +152,10 @@ impl Default for ClientState {
             transform_body: None,
             transform_turret: None,
             state: None,
+            speed: None,
+            health: None,
             shoot_cooldown: 0,
+            modules: None,
         }
     }
 }
@@ -147,3 +177,21 @@ impl ProjectileState {
         }
     }
 }
+
+#[derive(Debug, Reflect, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MineState {
+    pub mine_id: Entity,
+    pub owner_id: Entity,
+    pub transform: Transform,
+}
+
+impl MineState {
+    pub fn new(mine_id: Entity, owner_id: Entity, transform: Transform) -> Self {
+        MineState {
+            mine_id,
+            owner_id,
+            transform,
+        }
+    }
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+use super::collision_handling::components::Collider;
+
+/// The size of a mine (Vec3, x = width, y = height, z = depth)
+/// full-extents for x (width), z (depth) and y (height)
+pub const MINE_SIZE: Vec3 = Vec3::new(0.3, 0.1, 0.3);
+
+/// A mine deployed by a tank, hidden from enemies unless they are close enough to spot it
+#[derive(Debug, Component, Reflect, Clone, PartialEq)]
+#[reflect(Component)]
+pub struct MineMarker {
+    pub damage: f32,
+    pub owner: Entity,
+}
+
+pub fn setup_mine(trigger: Trigger<OnAdd, MineMarker>, mut commands: Commands) {
+    commands
+        .entity(trigger.entity())
+        .insert(Collider::new(MINE_SIZE / 2.0, 0.0));
+}
@@ -1,13 +1,19 @@
 use bevy::prelude::*;
 use collision_handling::MyCollisionHandlingPlugin;
-use game_state::{ClientState, LobbyGameState, PersonalizedClientGameState, ProjectileState};
-use player_handling::{PlayerState, ShootCooldown, TankBodyMarker, TankTurretMarker};
+use game_state::{
+    ClientState, LobbyGameState, MineState, PersonalizedClientGameState, ProjectileState,
+};
+use player_handling::{
+    DeployedMines, Health, PlayerState, ShootCooldown, TankBodyMarker, TankModules, TankMomentum,
+    TankRepair, TankTurretMarker,
+};
 use tank_types::TankType;
 
 pub mod collision_handling;
 pub mod common_components;
 pub mod common_systems;
 pub mod game_state;
+pub mod mine_handling;
 pub mod player_handling;
 pub mod projectile_handling;
 pub mod tank_types;
@@ -20,12 +26,19 @@ impl Plugin for MySharedGamePlugin {
             .register_type::<PersonalizedClientGameState>()
             .register_type::<ClientState>()
             .register_type::<ProjectileState>()
+            .register_type::<MineState>()
             .register_type::<TankBodyMarker>()
             .register_type::<TankTurretMarker>()
             .register_type::<ShootCooldown>()
+            .register_type::<Health>()
+            .register_type::<TankMomentum>()
+            .register_type::<TankModules>()
+            .register_type::<TankRepair>()
+            .register_type::<DeployedMines>()
             .register_type::<TankType>()
             .register_type::<PlayerState>()
             .register_type::<projectile_handling::ProjectileMarker>()
+            .register_type::<mine_handling::MineMarker>()
             .register_type::<common_components::DespawnTimer>()
             .register_type::<common_components::TickBasedDespawnTimer>()
             .add_plugins((MyCollisionHandlingPlugin,))
@@ -35,6 +48,7 @@ impl Plugin for MySharedGamePlugin {
                     .run_if(any_with_component::<common_components::DespawnTimer>),
             )
             .add_observer(projectile_handling::setup_projectile)
+            .add_observer(mine_handling::setup_mine)
             .add_observer(player_handling::setup_tank_body);
     }
 }
@@ -19,7 +19,16 @@ pub enum PlayerState {
 
 #[derive(Debug, Component, Reflect, Clone, PartialEq, Default)]
 #[reflect(Component)]
-#[require(ShootCooldown, PlayerState, WantedTransform)]
+#[require(
+    ShootCooldown,
+    PlayerState,
+    WantedTransform,
+    Health,
+    TankMomentum,
+    TankModules,
+    TankRepair,
+    DeployedMines
+)]
 pub struct TankBodyMarker {
     pub turret: Option<Entity>,
 }
@@ -47,6 +56,116 @@ impl Default for ShootCooldown {
     }
 }
 
+#[derive(Debug, Component, Reflect, Clone, PartialEq, Default)]
+#[reflect(Component)]
+pub struct Health {
+    pub health: f32,
+    pub max_health: f32,
+}
+
+impl Health {
+    pub fn new(max_health: f32) -> Self {
+        Self {
+            health: max_health,
+            max_health,
+        }
+    }
+
+    pub fn is_dead(&self) -> bool {
+        self.health <= 0.0
+    }
+
+    pub fn heal(&mut self, amount: f32) {
+        self.health = (self.health + amount).min(self.max_health);
+    }
+}
+
+/// Keeps track of when a tank can repair itself
+#[derive(Debug, Component, Reflect, Clone, PartialEq, Default)]
+#[reflect(Component)]
+pub struct TankRepair {
+    /// The amount of ticks since the tank was last hit or fired
+    pub ticks_out_of_combat: u32,
+    /// Whether the tank is actively repairing, which immobilizes it
+    pub repairing: bool,
+}
+
+impl TankRepair {
+    /// Called whenever the tank is hit or fires, restarting the out of combat timer
+    pub fn enter_combat(&mut self) {
+        self.ticks_out_of_combat = 0;
+    }
+}
+
+/// The current movement of a tank that has momentum (see `TankConfig::acceleration`)
+#[derive(Debug, Component, Reflect, Clone, PartialEq, Default)]
+#[reflect(Component)]
+pub struct TankMomentum {
+    /// The current speed per tick, negative when driving backwards
+    pub speed: f32,
+    /// The speed per tick the tank is accelerating or braking towards
+    pub throttle: f32,
+}
+
+impl TankMomentum {
+    /// Moves the speed towards the throttle by at most one tick of acceleration or deceleration
+    pub fn update_speed(&mut self, acceleration: f32, deceleration: f32) {
+        let is_speeding_up = self.speed == 0.0
+            || (self.throttle.signum() == self.speed.signum()
+                && self.throttle.abs() > self.speed.abs());
+        let change = if is_speeding_up {
+            acceleration
+        } else {
+            deceleration
+        };
+
+        self.speed = if self.throttle > self.speed {
+            (self.speed + change).min(self.throttle)
+        } else {
+            (self.speed - change).max(self.throttle)
+        };
+    }
+
+    pub fn stop(&mut self) {
+        self.speed = 0.0;
+        self.throttle = 0.0;
+    }
+}
+
+/// The amount of mines a tank has deployed in its current life (see `TankConfig::mine_allowance`)
+#[derive(Debug, Component, Reflect, Clone, PartialEq, Default)]
+#[reflect(Component)]
+pub struct DeployedMines {
+    pub count: u32,
+}
+
+/// The state of the modules of a tank that can be disabled by critical hits
+#[derive(Debug, Component, Reflect, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[reflect(Component)]
+#[serde(rename_all = "camelCase")]
+pub struct TankModules {
+    /// Ticks until the tracks are repaired. The tank can't move or rotate its body while they are broken
+    pub tracks_disabled_ticks: u32,
+    /// Ticks until the turret ring is repaired. The turret can't rotate while it is broken
+    pub turret_ring_disabled_ticks: u32,
+}
+
+impl TankModules {
+    pub fn tracks_working(&self) -> bool {
+        self.tracks_disabled_ticks == 0
+    }
+
+    pub fn turret_ring_working(&self) -> bool {
+        self.turret_ring_disabled_ticks == 0
+    }
+
+    /// Repairs all modules by one tick
+    pub fn tick(&mut self) {
+        self.tracks_disabled_ticks = self.tracks_disabled_ticks.saturating_sub(1);
+        self.turret_ring_disabled_ticks = self.turret_ring_disabled_ticks.saturating_sub(1);
+    }
+}
+
 pub fn setup_tank_body(
     trigger: Trigger<OnAdd, TankBodyMarker>,
     mut commands: Commands,
@@ -62,14 +181,13 @@ pub fn setup_tank_body(
         .expect("TankConfig should exist");
 
     commands.entity(tank_body_entity).insert((
-        Collider {
-            half_size: tank_config.size / 2.0,
-            max_slope: tank_config.max_slope,
-        },
+        Collider::new(tank_config.size / 2.0, tank_config.max_slope)
+            .with_max_drop(tank_config.max_drop_height),
         CollisionLayer::new(&[0]),
         ShootCooldown {
             ticks_left: 0,
             ticks_cooldown: tank_config.shoot_cooldown,
         },
+        Health::new(tank_config.max_health),
     ));
 }
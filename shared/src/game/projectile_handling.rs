@@ -43,6 +43,7 @@ pub fn setup_projectile(
         Collider {
             half_size: tank_config.projectile_size / 2.0,
             max_slope: 0.0,
+            max_drop: 0.0,
         },
     ));
 }
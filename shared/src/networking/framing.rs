@@ -0,0 +1,203 @@
+use std::{
+    fmt,
+    io::{self, Read, Write},
+};
+
+use bevy::prelude::*;
+
+/// The version of the wire protocol, sent in front of the first frame of a connection
+/// Has to be increased whenever the framing or the messages change in an incompatible way
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// The size of the protocol version header and of the length prefix in bytes
+pub const HEADER_SIZE: usize = 4;
+
+/// How many bytes are read from the stream at once
+const READ_CHUNK_SIZE: usize = 4096;
+
+/// Frames the messages sent over a connection, used by the server and the clients
+///
+/// Every frame is a 4-byte length prefix followed by the payload (an array of MessageContainers, see `MessageCodec`).
+/// All integers are encoded as big-endian (network byte order).
+/// The first frame sent in each direction is preceded by the 4-byte protocol version:
+/// `[version: u32][length: u32][payload]`, followed by `[length: u32][payload]` for all other frames.
+#[derive(Debug, Component, Default)]
+pub struct FrameCodec {
+    version_sent: bool,
+    /// Bytes received but not yet decoded, as frames can arrive split over multiple reads
+    receive_buffer: Vec<u8>,
+    /// The protocol version of the other side, known once its first frame has been read
+    pub peer_version: Option<u32>,
+    /// Frames with a larger payload are discarded, None allows any size
+    max_frame_size: Option<u32>,
+    /// How many bytes of a discarded frame still have to be skipped
+    bytes_to_discard: usize,
+}
+
+/// Returned (wrapped in an `io::Error`) by `FrameCodec::next_frame` for a frame exceeding the maximum size
+/// The frame is discarded, the connection can still be used afterwards
+#[derive(Debug)]
+pub struct FrameTooLarge {
+    pub size: u32,
+    pub max_size: u32,
+}
+
+impl fmt::Display for FrameTooLarge {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Frame of {} bytes exceeds the maximum of {} bytes",
+            self.size, self.max_size
+        )
+    }
+}
+
+impl std::error::Error for FrameTooLarge {}
+
+impl FrameTooLarge {
+    /// Returns the `FrameTooLarge` wrapped in the error, if it is one
+    pub fn from_io_error(error: &io::Error) -> Option<&Self> {
+        error.get_ref().and_then(|inner| inner.downcast_ref())
+    }
+}
+
+impl FrameCodec {
+    pub fn with_max_frame_size(max_frame_size: u32) -> Self {
+        Self {
+            max_frame_size: Some(max_frame_size),
+            ..default()
+        }
+    }
+
+    /// Encodes the payload into a frame, prefixed with the protocol version if it is the first frame
+    pub fn encode_frame(&mut self, payload: &[u8]) -> Vec<u8> {
+        let mut frame = Vec::with_capacity(HEADER_SIZE * 2 + payload.len());
+        if !self.version_sent {
+            frame.extend_from_slice(&PROTOCOL_VERSION.to_be_bytes());
+            self.version_sent = true;
+        }
+        frame.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        frame.extend_from_slice(payload);
+
+        frame
+    }
+
+    pub fn write_frame(&mut self, stream: &mut impl Write, payload: &[u8]) -> io::Result<()> {
+        let frame = self.encode_frame(payload);
+        stream.write_all(&frame)
+    }
+
+    /// Reads all bytes available on the non-blocking stream into the receive buffer
+    /// Incomplete frames stay in the buffer until the rest of them arrived
+    /// With a maximum frame size, reading stops once the buffer could hold a complete frame, the rest is read on the next call
+    pub fn receive(&mut self, stream: &mut impl Read) -> io::Result<()> {
+        let mut chunk = [0u8; READ_CHUNK_SIZE];
+        loop {
+            if let Some(max_frame_size) = self.max_frame_size {
+                if self.receive_buffer.len() >= max_frame_size as usize + HEADER_SIZE * 2 {
+                    return Ok(());
+                }
+            }
+
+            match stream.read(&mut chunk) {
+                Ok(0) => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::UnexpectedEof,
+                        "Connection closed by peer",
+                    ))
+                }
+                Ok(read) => self.receive_buffer.extend_from_slice(&chunk[..read]),
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => return Ok(()),
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Reads all bytes available on the non-blocking stream and returns the payloads of all complete frames
+    pub fn read_frames(&mut self, stream: &mut impl Read) -> io::Result<Vec<Vec<u8>>> {
+        self.receive(stream)?;
+
+        let mut frames = Vec::new();
+        while let Some(frame) = self.next_frame()? {
+            frames.push(frame);
+        }
+
+        Ok(frames)
+    }
+
+    /// Returns the payload of the next complete, non-empty frame in the receive buffer
+    pub fn next_frame(&mut self) -> io::Result<Option<Vec<u8>>> {
+        while let Some(frame) = self.next_buffered_frame()? {
+            if !frame.is_empty() {
+                return Ok(Some(frame));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Takes the next complete frame out of the receive buffer
+    /// Reads the protocol version in front of the first frame and checks the size of every frame
+    fn next_buffered_frame(&mut self) -> io::Result<Option<Vec<u8>>> {
+        // Skip what arrived of a discarded frame, without ever buffering all of it
+        if self.bytes_to_discard > 0 {
+            let discarded = self.bytes_to_discard.min(self.receive_buffer.len());
+            self.receive_buffer.drain(..discarded);
+            self.bytes_to_discard -= discarded;
+            if self.bytes_to_discard > 0 {
+                return Ok(None);
+            }
+        }
+
+        if self.peer_version.is_none() {
+            let version = match read_u32(&self.receive_buffer) {
+                Some(version) => version,
+                None => return Ok(None),
+            };
+            // A different version is not rejected here, the server answers the first contact with an error instead
+            if version != PROTOCOL_VERSION {
+                warn!(
+                    "Peer speaks protocol version {}, expected {}",
+                    version, PROTOCOL_VERSION
+                );
+            }
+            self.receive_buffer.drain(..HEADER_SIZE);
+            self.peer_version = Some(version);
+        }
+
+        let msg_len = match read_u32(&self.receive_buffer) {
+            Some(msg_len) => msg_len,
+            None => return Ok(None),
+        };
+        if let Some(max_frame_size) = self.max_frame_size {
+            if msg_len > max_frame_size {
+                self.receive_buffer.drain(..HEADER_SIZE);
+                self.bytes_to_discard = msg_len as usize;
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    FrameTooLarge {
+                        size: msg_len,
+                        max_size: max_frame_size,
+                    },
+                ));
+            }
+        }
+        let frame_len = HEADER_SIZE + msg_len as usize;
+        if self.receive_buffer.len() < frame_len {
+            return Ok(None);
+        }
+
+        let frame = self.receive_buffer[HEADER_SIZE..frame_len].to_vec();
+        self.receive_buffer.drain(..frame_len);
+
+        Ok(Some(frame))
+    }
+}
+
+/// Decodes a big-endian u32 from the start of the buffer, if enough bytes are available
+fn read_u32(buffer: &[u8]) -> Option<u32> {
+    buffer
+        .get(..HEADER_SIZE)
+        .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
@@ -1,6 +1,6 @@
 use bevy::prelude::*;
 
-use crate::game::projectile_handling::ProjectileMarker;
+use crate::game::{mine_handling::MineMarker, projectile_handling::ProjectileMarker};
 
 use super::{InLobby, MyLobbies, MyLobby};
 
@@ -8,7 +8,8 @@ pub fn add_observers_on_lobby_join(trigger: Trigger<OnAdd, InLobby>, mut command
     commands
         .entity(trigger.entity())
         .observe(lobby_despawn)
-        .observe(remove_projectile_on_projectile_despawn);
+        .observe(remove_projectile_on_projectile_despawn)
+        .observe(remove_mine_on_mine_despawn);
 }
 
 fn lobby_despawn(trigger: Trigger<OnRemove, MyLobby>, mut commands: Commands) {
@@ -34,3 +35,16 @@ pub fn remove_projectile_on_projectile_despawn(
         }
     }
 }
+
+pub fn remove_mine_on_mine_despawn(
+    trigger: Trigger<OnRemove, MineMarker>,
+    mut lobby: Query<&mut MyLobby>,
+    mines: Query<&InLobby>,
+) {
+    let mine_entity = trigger.entity();
+    if let Ok(in_lobby) = mines.get(mine_entity) {
+        if let Ok(mut lobby) = lobby.get_mut(in_lobby.0) {
+            lobby.remove_mine(mine_entity);
+        }
+    }
+}
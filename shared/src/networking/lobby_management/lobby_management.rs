@@ -4,8 +4,11 @@ use crate::{
     asset_handling::config::ServerConfig,
     game::game_state::LobbyGameState,
     networking::{
-        lobby_management::PlayerRemovedFromLobbyTrigger,
-        messages::message_data::first_contact::ClientType,
+        lobby_management::{LobbyRosterChangedTrigger, PlayerRemovedFromLobbyTrigger},
+        messages::message_data::{
+            first_contact::{ClientType, FirstContactData},
+            lobby_membership::LobbyRemovalReason,
+        },
     },
 };
 
@@ -26,27 +29,29 @@ pub struct LobbyManagementSystemParam<'w, 's> {
 }
 
 impl<'w, 's> LobbyManagementSystemParam<'w, 's> {
+    /// Creates the lobby with the settings of the first contact, if it doesn't exist yet
     pub fn get_or_insert_lobby_entity(
         &mut self,
-        lobby_id: &str,
-        map_name: Option<&str>,
+        first_contact: &FirstContactData,
         commands: &mut Commands,
         server_config: &ServerConfig,
     ) -> Result<Entity, ()> {
+        let lobby_id = &first_contact.lobby_name;
         let lobby_entity_entry = self.lobby_resource.lobbies.entry(lobby_id.to_string());
 
         match lobby_entity_entry {
             Entry::Occupied(entry) => Ok(*entry.get()),
             Entry::Vacant(entry) => {
-                if let Some(map_name) = map_name {
+                if let Some(map_name) = &first_contact.map_name {
                     let map_name = map_name.to_string();
 
                     let entity = commands
-                        .spawn(MyLobby::new(
-                            lobby_id.to_string(),
-                            map_name,
-                            server_config.tick_rate,
-                        ))
+                        .spawn(
+                            MyLobby::new(lobby_id.to_string(), map_name, server_config.tick_rate)
+                                .with_password(first_contact.lobby_password.clone())
+                                .with_team_join_codes(first_contact.team_join_codes.clone())
+                                .with_auto_start(first_contact.auto_start.clone()),
+                        )
                         .id();
 
                     entry.insert(entity);
@@ -63,10 +68,11 @@ impl<'w, 's> LobbyManagementSystemParam<'w, 's> {
     pub fn remove_player_from_lobby(
         &mut self,
         player: Entity,
-        lobby: Entity,
+        lobby_entity: Entity,
+        reason: LobbyRemovalReason,
         commands: &mut Commands,
     ) {
-        if let Ok((_, mut lobby, _)) = self.lobby_entities.get_mut(lobby) {
+        if let Ok((_, mut lobby, _)) = self.lobby_entities.get_mut(lobby_entity) {
             lobby
                 .players
                 .retain(|(_, x, _)| if *x == player { false } else { true });
@@ -75,16 +81,36 @@ impl<'w, 's> LobbyManagementSystemParam<'w, 's> {
                 .spectators
                 .retain(|&x| if x == player { false } else { true });
 
+            lobby.ready_players.retain(|&x| x != player);
+
+            // Hand the host role over to the next member, preferring players over spectators
+            if lobby.is_host(player) {
+                lobby.host = lobby
+                    .players
+                    .iter()
+                    .filter(|(_, _, client_type)| *client_type != ClientType::Dummy)
+                    .map(|(_, player, _)| *player)
+                    .chain(lobby.spectators.iter().cloned())
+                    .next();
+            }
+
             // Also remove from team
             if let Some(ref mut map_config) = &mut lobby.map_config {
                 map_config.remove_player_from_team(player);
             }
 
-            commands.trigger_targets(PlayerRemovedFromLobbyTrigger, player);
+            commands.trigger_targets(
+                PlayerRemovedFromLobbyTrigger {
+                    lobby_name: lobby.lobby_name.clone(),
+                    reason,
+                },
+                player,
+            );
+            commands.trigger_targets(LobbyRosterChangedTrigger, lobby_entity);
         } else {
             error!(
                 "Failed to get lobby for lobby entity: {}, cannot remove player {} from lobby",
-                lobby, player
+                lobby_entity, player
             );
         }
 
@@ -148,7 +174,13 @@ impl<'w, 's> LobbyManagementSystemParam<'w, 's> {
                             "Removing player/spectator {} from lobby {}...",
                             player, lobby_entity
                         );
-                        commands.trigger_targets(PlayerRemovedFromLobbyTrigger, *player);
+                        commands.trigger_targets(
+                            PlayerRemovedFromLobbyTrigger {
+                                lobby_name: lobby.lobby_name.clone(),
+                                reason: LobbyRemovalReason::LobbyClosed,
+                            },
+                            *player,
+                        );
                     }
 
                     commands.entity(lobby_entity).despawn_recursive();
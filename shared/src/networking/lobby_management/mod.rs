@@ -1,17 +1,23 @@
-use std::time::Duration;
+use std::{cmp::Ordering, time::Duration};
 
 use bevy::{prelude::*, utils::HashMap};
 use lobby_management::LobbyManagementSystemParam;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     asset_handling::{
         config::ServerConfigSystemParam,
-        maps::{MapConfig, MapConfigSystemParam},
+        maps::{MapConfig, MapConfigSystemParam, TeamConfig},
     },
     game::game_state::LobbyGameState,
     networking::messages::{
         message_container::{MessageContainer, MessageTarget, NetworkMessageType},
-        message_data::{message_error_types::ErrorMessageTypes, text_data::TextDataWrapper},
+        message_data::{
+            lobby_membership::{LobbyRemovalReason, RemovedFromLobby},
+            message_error_types::ErrorMessageTypes,
+            start_game_config::StartGameConfig,
+            text_data::TextDataWrapper,
+        },
         message_queue::OutMessageQueue,
     },
 };
@@ -32,6 +38,7 @@ impl Plugin for MyLobbyManagementPlugin {
             .init_resource::<MyLobbies>()
             .register_type::<MyLobby>()
             .register_type::<InLobby>()
+            .register_type::<SkillRating>()
             .register_type::<LobbyState>()
             .register_type::<AwaitingFirstContact>()
             .add_observer(finish_setting_up_lobby)
@@ -62,8 +69,20 @@ pub struct InTeam(pub String);
 #[reflect(Component)]
 pub struct InLobby(pub Entity);
 
+/// The skill of a player, as told in its first contact, used to balance the teams
+#[derive(Debug, Reflect, Component, Deref, DerefMut, Clone, Copy)]
+#[reflect(Component)]
+pub struct SkillRating(pub f32);
+
+#[derive(Debug, Event)]
+pub struct PlayerRemovedFromLobbyTrigger {
+    pub lobby_name: String,
+    pub reason: LobbyRemovalReason,
+}
+
+/// Triggered on a lobby whenever its members changed, the server sends the new roster to all of them
 #[derive(Debug, Event)]
-pub struct PlayerRemovedFromLobbyTrigger;
+pub struct LobbyRosterChangedTrigger;
 
 #[derive(Debug, Event)]
 pub struct PlayerWantsToJoinLobbyTrigger {
@@ -72,6 +91,10 @@ pub struct PlayerWantsToJoinLobbyTrigger {
     pub lobby: Entity,
     pub player_type: ClientType,
     pub team_name: Option<String>,
+    pub lobby_password: Option<String>,
+    pub team_join_code: Option<String>,
+    /// Whether the player knows the admin password of the server
+    pub is_admin: bool,
 }
 
 #[derive(Default, Resource, Reflect, Debug)]
@@ -104,10 +127,23 @@ pub struct MyLobby {
     pub players: Vec<(String, Entity, ClientType)>,
     pub spectators: Vec<Entity>,
     pub projectiles: Vec<Entity>,
+    pub mines: Vec<Entity>,
 
     pub map_name: String,
     pub map_config: Option<MapConfig>,
 
+    /// The member allowed to kick members, force a start and change the settings
+    /// The first member to join, or the last admin that joined
+    pub host: Option<Entity>,
+    /// Everyone joining the lobby has to know the password, if set
+    pub password: Option<String>,
+    /// Players joining a team with a join code have to know it (team name -> join code)
+    pub team_join_codes: HashMap<String, String>,
+    /// The players that are ready for the game to start
+    pub ready_players: Vec<Entity>,
+    /// The config to start the lobby with as soon as all players are ready, None disables starting automatically
+    pub auto_start: Option<StartGameConfig>,
+
     /// Timer for ticking the lobby
     pub tick_timer: Timer,
     /// The currently, finished tick
@@ -127,10 +163,17 @@ impl MyLobby {
             players: Vec::new(),
             spectators: Vec::new(),
             projectiles: Vec::new(),
+            mines: Vec::new(),
 
             map_name,
             map_config: None,
 
+            host: None,
+            password: None,
+            team_join_codes: HashMap::new(),
+            ready_players: Vec::new(),
+            auto_start: None,
+
             tick_timer: Timer::from_seconds(time_per_tick, TimerMode::Repeating),
             tick_processed: 0,
 
@@ -148,6 +191,52 @@ impl MyLobby {
         self
     }
 
+    pub fn with_password(mut self, password: Option<String>) -> Self {
+        self.password = password;
+        self
+    }
+
+    pub fn with_team_join_codes(mut self, team_join_codes: HashMap<String, String>) -> Self {
+        self.team_join_codes = team_join_codes;
+        self
+    }
+
+    pub fn with_auto_start(mut self, auto_start: Option<StartGameConfig>) -> Self {
+        self.auto_start = auto_start;
+        self
+    }
+
+    pub fn set_ready(&mut self, player: Entity, ready: bool) {
+        self.ready_players.retain(|&p| p != player);
+        if ready {
+            self.ready_players.push(player);
+        }
+    }
+
+    pub fn is_ready(&self, player: Entity) -> bool {
+        self.ready_players.contains(&player)
+    }
+
+    pub fn is_host(&self, client: Entity) -> bool {
+        self.host == Some(client)
+    }
+
+    pub fn is_member(&self, client: Entity) -> bool {
+        self.players.iter().any(|(_, player, _)| *player == client)
+            || self.spectators.contains(&client)
+    }
+
+    /// Whether there are players and all of them (not counting dummies) are ready
+    pub fn all_players_ready(&self) -> bool {
+        let mut players = self
+            .players
+            .iter()
+            .filter(|(_, _, client_type)| *client_type != ClientType::Dummy)
+            .peekable();
+
+        players.peek().is_some() && players.all(|(_, player, _)| self.is_ready(*player))
+    }
+
     pub fn get_team(&self, team_name: &str) -> Option<&Vec<Entity>> {
         self.map_config
             .as_ref()
@@ -157,9 +246,14 @@ impl MyLobby {
     pub fn remove_projectile(&mut self, projectile: Entity) {
         self.projectiles.retain(|&p| p != projectile);
     }
+
+    pub fn remove_mine(&mut self, mine: Entity) {
+        self.mines.retain(|&m| m != mine);
+    }
 }
 
-#[derive(Debug, Reflect, Default, PartialEq)]
+#[derive(Debug, Reflect, Default, PartialEq, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
 pub enum LobbyState {
     #[default]
     SettingUp,
@@ -171,14 +265,28 @@ pub enum LobbyState {
 pub fn remove_player_from_lobby(
     trigger: Trigger<PlayerRemovedFromLobbyTrigger>,
     mut commands: Commands,
+    mut message_queues: Query<&mut ImmediateOutMessageQueue>,
     server_config: ServerConfigSystemParam,
 ) {
     let server_config = server_config.server_config();
 
     let player = trigger.entity();
-    info!("Player {} removed from lobby", player);
-
-    // TODO: let player know they've been removed from the lobby?
+    let PlayerRemovedFromLobbyTrigger { lobby_name, reason } = trigger.event();
+    info!(
+        "Player {} removed from lobby \"{}\": {:?}",
+        player, lobby_name, reason
+    );
+
+    // Disconnected clients might already be gone
+    if let Ok(mut queue) = message_queues.get_mut(player) {
+        queue.push_back(MessageContainer::new(
+            MessageTarget::Client(player),
+            NetworkMessageType::RemovedFromLobby(RemovedFromLobby {
+                lobby_name: lobby_name.clone(),
+                reason: reason.clone(),
+            }),
+        ));
+    }
 
     commands
         .entity(player)
@@ -194,6 +302,7 @@ fn adding_player_to_lobby(
     mut lobby_management: LobbyManagementSystemParam,
     mut commands: Commands,
     mut player_immediate_message_queues: Query<&mut ImmediateOutMessageQueue>,
+    skill_ratings: Query<&SkillRating>,
 ) {
     let PlayerWantsToJoinLobbyTrigger {
         player,
@@ -201,13 +310,18 @@ fn adding_player_to_lobby(
         player_type,
         team_name,
         player_name,
+        lobby_password,
+        team_join_code,
+        is_admin,
     } = trigger.event();
 
     if let Ok(mut lobby) = lobby_management.get_lobby_mut(*lobby_entity) {
         let mut queue = player_immediate_message_queues.get_mut(*player).unwrap();
 
-        match lobby.state {
-            LobbyState::InProgress | LobbyState::Finished => {
+        match (&lobby.state, player_type) {
+            // Spectators can still watch a running match
+            (LobbyState::InProgress, ClientType::Spectator) => {}
+            (LobbyState::InProgress | LobbyState::Finished, _) => {
                 error!(
                     "Player {:?} wants to join lobby {:?} but it is in state {:?}",
                     player, lobby_entity, lobby.state
@@ -227,51 +341,101 @@ fn adding_player_to_lobby(
             _ => {}
         }
 
+        if lobby.password.is_some() && *lobby_password != lobby.password {
+            error!(
+                "Player {:?} wants to join lobby {:?} with a wrong password",
+                player, lobby_entity
+            );
+            queue.push_back(MessageContainer::new(
+                MessageTarget::Client(*player),
+                NetworkMessageType::MessageError(ErrorMessageTypes::WrongLobbyPassword(
+                    "Lobby can't be joined without the correct password".to_string(),
+                )),
+            ));
+
+            return;
+        }
+
         match player_type {
             ClientType::Player => {
-                if let Some(team_name) = team_name {
-                    lobby
-                        .players
-                        .push((player_name.clone(), *player, player_type.clone()));
-
-                    match lobby
-                        .map_config
-                        .as_mut()
-                        .expect("Map config should be set up by now")
-                        .insert_player_into_team(team_name, *player)
-                    {
-                        Ok(_) => {
-                            commands
-                                .entity(*player)
-                                .insert((InTeam(team_name.clone()),));
-
-                            queue.push_back(MessageContainer::new(
-                                MessageTarget::Client(*player),
-                                NetworkMessageType::SuccessFullyJoinedLobby(TextDataWrapper::new(
-                                    format!("Successfully joined lobby on team {}", team_name),
-                                )),
-                            ));
+                let team_name = match team_name {
+                    Some(team_name) => team_name.clone(),
+                    None => match balanced_team(&lobby, *player, team_join_code, &skill_ratings) {
+                        Some(team_name) => {
+                            info!(
+                                "Player {:?} didn't ask for a team, putting it into team {}",
+                                player, team_name
+                            );
+                            team_name
                         }
-                        Err(err) => {
-                            error!("Failed to add player to team {}: {:?}", team_name, err);
+                        None => {
+                            error!(
+                                "Player {:?} wants to join lobby {:?}, but all teams are full",
+                                player, lobby_entity
+                            );
                             queue.push_back(MessageContainer::new(
                                 MessageTarget::Client(*player),
-                                NetworkMessageType::MessageError(err),
+                                NetworkMessageType::MessageError(ErrorMessageTypes::TeamFull(
+                                    "All teams of the lobby are full".to_string(),
+                                )),
                             ));
 
                             return;
                         }
+                    },
+                };
+
+                if let Some(expected_join_code) = lobby.team_join_codes.get(&team_name) {
+                    if team_join_code.as_ref() != Some(expected_join_code) {
+                        error!(
+                            "Player {:?} wants to join team {} with a wrong join code",
+                            player, team_name
+                        );
+                        queue.push_back(MessageContainer::new(
+                            MessageTarget::Client(*player),
+                            NetworkMessageType::MessageError(ErrorMessageTypes::WrongTeamJoinCode(
+                                format!(
+                                    "Team {} can't be joined without the correct join code",
+                                    team_name
+                                ),
+                            )),
+                        ));
+
+                        return;
+                    }
+                }
+
+                lobby
+                    .players
+                    .push((player_name.clone(), *player, player_type.clone()));
+
+                match lobby
+                    .map_config
+                    .as_mut()
+                    .expect("Map config should be set up by now")
+                    .insert_player_into_team(&team_name, *player)
+                {
+                    Ok(_) => {
+                        commands
+                            .entity(*player)
+                            .insert((InTeam(team_name.clone()),));
+
+                        queue.push_back(MessageContainer::new(
+                            MessageTarget::Client(*player),
+                            NetworkMessageType::SuccessFullyJoinedLobby(TextDataWrapper::new(
+                                format!("Successfully joined lobby on team {}", team_name),
+                            )),
+                        ));
+                    }
+                    Err(err) => {
+                        error!("Failed to add player to team {}: {:?}", team_name, err);
+                        queue.push_back(MessageContainer::new(
+                            MessageTarget::Client(*player),
+                            NetworkMessageType::MessageError(err),
+                        ));
+
+                        return;
                     }
-                } else {
-                    error!("Player wants to join lobby without specifying a team name");
-                    queue.push_back(MessageContainer::new(
-                        MessageTarget::Client(*player),
-                        NetworkMessageType::MessageError(ErrorMessageTypes::LobbyManagementError(
-                            "Player wants to join lobby without specifying a team name".to_string(),
-                        )),
-                    ));
-
-                    return;
                 }
             }
             ClientType::Spectator => {
@@ -280,13 +444,78 @@ fn adding_player_to_lobby(
             ClientType::Dummy => unimplemented!("Dummy clients should not be able to join lobbies"),
         }
 
+        if lobby.host.is_none() || *is_admin {
+            info!(
+                "Client {:?} is the host of lobby {:?}",
+                player, lobby_entity
+            );
+            lobby.host = Some(*player);
+        }
+
         commands
             .entity(*player)
             .insert((InLobby(*lobby_entity),))
             .remove::<AwaitingFirstContact>();
+
+        commands.trigger_targets(LobbyRosterChangedTrigger, *lobby_entity);
     }
 }
 
+/// The team for a player that didn't ask for one, skipping full teams and teams with an unknown join code
+/// Players join the team with the most free slots, rated players break ties by the lowest average rating
+fn balanced_team(
+    lobby: &MyLobby,
+    player: Entity,
+    team_join_code: &Option<String>,
+    skill_ratings: &Query<&SkillRating>,
+) -> Option<String> {
+    // Only rated players count, a team without any has an average of 0
+    let average_rating = |players: &Vec<Entity>| {
+        let ratings = players
+            .iter()
+            .filter_map(|player| skill_ratings.get(*player).ok())
+            .map(|rating| **rating)
+            .collect::<Vec<_>>();
+        if ratings.is_empty() {
+            0.0
+        } else {
+            ratings.iter().sum::<f32>() / ratings.len() as f32
+        }
+    };
+    let is_rated = skill_ratings.contains(player);
+
+    let mut teams = lobby
+        .map_config
+        .as_ref()
+        .expect("Map config should be set up by now")
+        .teams
+        .iter()
+        .filter(|(_, team)| team.players.len() < team.max_players)
+        .filter(
+            |(team_name, _)| match lobby.team_join_codes.get(*team_name) {
+                Some(expected_join_code) => team_join_code.as_ref() == Some(expected_join_code),
+                None => true,
+            },
+        )
+        .collect::<Vec<_>>();
+    // Sorted by name first, so ties always end up in the same team
+    teams.sort_by_key(|(team_name, _)| *team_name);
+
+    teams
+        .into_iter()
+        .min_by(|(_, a), (_, b)| {
+            let free_slots = |team: &TeamConfig| team.max_players - team.players.len();
+            free_slots(b).cmp(&free_slots(a)).then_with(|| {
+                if is_rated {
+                    average_rating(&a.players).total_cmp(&average_rating(&b.players))
+                } else {
+                    Ordering::Equal
+                }
+            })
+        })
+        .map(|(team_name, _)| team_name.clone())
+}
+
 fn finish_setting_up_lobby(
     trigger: Trigger<OnAdd, MyLobby>,
     mut lobby_management: LobbyManagementSystemParam,
@@ -0,0 +1,68 @@
+use bevy::prelude::*;
+
+use super::messages::message_container::MessageContainer;
+
+/// Encodes and decodes the batches of MessageContainers sent as the payload of a frame
+pub trait MessageCodec: Send + Sync {
+    fn encode(&self, messages: &[MessageContainer]) -> Result<Vec<u8>, String>;
+    fn decode(&self, payload: &[u8]) -> Result<Vec<MessageContainer>, String>;
+}
+
+/// The default codec, messages are sent as a UTF-8 JSON array
+pub struct JsonCodec;
+
+/// The byte order mark some clients put in front of UTF-8 text
+const UTF8_BOM: &[u8] = b"\xEF\xBB\xBF";
+
+impl MessageCodec for JsonCodec {
+    fn encode(&self, messages: &[MessageContainer]) -> Result<Vec<u8>, String> {
+        serde_json::to_vec(messages).map_err(|e| e.to_string())
+    }
+
+    fn decode(&self, payload: &[u8]) -> Result<Vec<MessageContainer>, String> {
+        let payload = payload.strip_prefix(UTF8_BOM).unwrap_or(payload);
+        serde_json::from_slice(payload).map_err(|e| e.to_string())
+    }
+}
+
+/// A compact binary codec, messages are sent as a MessagePack array with named fields
+/// Has to be negotiated at first contact (see `ProtocolCapability::BinaryEncoding`)
+pub struct MessagePackCodec;
+
+impl MessageCodec for MessagePackCodec {
+    fn encode(&self, messages: &[MessageContainer]) -> Result<Vec<u8>, String> {
+        rmp_serde::to_vec_named(messages).map_err(|e| e.to_string())
+    }
+
+    fn decode(&self, payload: &[u8]) -> Result<Vec<MessageContainer>, String> {
+        rmp_serde::from_slice(payload).map_err(|e| e.to_string())
+    }
+}
+
+/// The encoding used for the messages sent to a client
+#[derive(Debug, Component, Reflect, Clone, Copy, PartialEq, Eq, Default)]
+#[reflect(Component)]
+pub enum MessageEncoding {
+    #[default]
+    Json,
+    MessagePack,
+}
+
+impl MessageEncoding {
+    pub fn codec(&self) -> &'static dyn MessageCodec {
+        match self {
+            MessageEncoding::Json => &JsonCodec,
+            MessageEncoding::MessagePack => &MessagePackCodec,
+        }
+    }
+
+    /// Detects the encoding of a received payload, so messages sent before the negotiation finished can still be read
+    /// JSON arrays start with '[' after an optional BOM and whitespace, MessagePack arrays never start with any of these
+    pub fn detect(payload: &[u8]) -> Self {
+        let payload = payload.strip_prefix(UTF8_BOM).unwrap_or(payload);
+        match payload.iter().find(|byte| !byte.is_ascii_whitespace()) {
+            Some(b'[') => MessageEncoding::Json,
+            _ => MessageEncoding::MessagePack,
+        }
+    }
+}
@@ -9,13 +9,23 @@ use crate::networking::{
 
 use super::message_data::{
     first_contact::FirstContactData,
+    game_finished::GameFinished,
     game_starts::GameStarts,
-    game_state::GameState,
+    game_state::{AcknowledgeGameState, GameState, GameStateDelta, RequestFullGameState},
+    lobby_host::{ChangeLobbySettings, KickMember, Rematch, TransferHost},
+    lobby_list::{LobbyList, RequestLobbyList},
+    lobby_membership::{
+        LeaveLobby, MemberLeftLobby, MemberSwitchedTeam, RemovedFromLobby, SwitchTeam,
+    },
+    lobby_roster::{LobbyRoster, SetReady},
     message_error_types::ErrorMessageTypes,
+    protocol_negotiation::NegotiatedProtocol,
+    session_token::SessionToken,
     start_game_config::StartGameConfig,
     tank_messages::{
-        move_tank::MoveTankCommand, rotate_tank_body::RotateTankBodyCommand,
-        rotate_tank_turret::RotateTankTurretCommand, shoot::ShootCommand,
+        deploy_mine::DeployMineCommand, move_tank::MoveTankCommand, repair::RepairCommand,
+        rotate_tank_body::RotateTankBodyCommand, rotate_tank_turret::RotateTankTurretCommand,
+        shoot::ShootCommand,
     },
     text_data::TextDataWrapper,
 };
@@ -57,10 +67,32 @@ use super::message_data::{
             /// Used to determine the client type and the lobby to join or create and the team to join and other initial information
             #[target(ServerOnly)]
             FirstContact(FirstContactData),
+            /// Asks for all lobbies and maps on the server, can be sent before the first contact
+            #[target(ServerOnly)]
+            RequestLobbyList(RequestLobbyList),
+            /// The answer to a lobby list request
+            /// Can not be sent by a client, only by the server
+            LobbyList(LobbyList),
+            /// The answer to the first contact, containing the protocol version and the capabilities that will be used
+            /// Can not be sent by a client, only by the server
+            ProtocolNegotiated(NegotiatedProtocol),
+            /// The token to reconnect with, sent to a player when joining a lobby and when reconnecting
+            /// Can not be sent by a client, only by the server
+            SessionToken(SessionToken),
             /// The current game state, sent each tick to the clients
             /// Each client could receive a different state, depending on their view of the game
             /// Can not be sent by a client, only by the server
             GameState(GameState),
+            /// The changes to the game state since the last state the client acknowledged
+            /// Sent instead of the GameState to clients using delta states
+            /// Can not be sent by a client, only by the server
+            GameStateDelta(GameStateDelta),
+            /// Sent by clients using delta states to acknowledge a received game state
+            #[target(ToSelf)]
+            AcknowledgeGameState(AcknowledgeGameState),
+            /// Sent by clients using delta states to receive the next game state in full
+            #[target(ToSelf)]
+            RequestFullGameState(RequestFullGameState),
             /// A simple Text Message
             /// Can be sent to a single client, everyone in the team or everyone in the lobby
             /// The server does not do anything with this message, it only forwards it to the specified targets
@@ -77,7 +109,11 @@ use super::message_data::{
             /// Can not be sent by a client, only by the server
             #[serde(rename = "GameConfig")]
             GameStarts(GameStarts),
+            /// Sent to all members of a lobby when the game finished, contains the winning team
+            /// Can not be sent by a client, only by the server
+            GameFinished(GameFinished),
             /// Sent to the lobby by a client to start the game
+            /// Only the host can force a start before all players are ready
             /// Can only be sent to the lobby directly
             #[target(ToLobbyDirectly)]
             StartGame(StartGameConfig),
@@ -86,6 +122,39 @@ use super::message_data::{
             /// We need to rename it, because we don't want it to be serialized as "TextDataWrapper"
             #[serde(rename = "SuccessfullyJoinedLobby")]
             SuccessFullyJoinedLobby(TextDataWrapper),
+            /// Sent to the lobby by a client to leave it, only before the game started
+            #[target(ToLobbyDirectly)]
+            LeaveLobby(LeaveLobby),
+            /// Sent to the lobby by a player to switch its team, only before the game started
+            #[target(ToLobbyDirectly)]
+            SwitchTeam(SwitchTeam),
+            /// Sent to all members of a lobby when a client left it
+            /// Can not be sent by a client, only by the server
+            MemberLeftLobby(MemberLeftLobby),
+            /// Sent to all members of a lobby when a player switched its team
+            /// Can not be sent by a client, only by the server
+            MemberSwitchedTeam(MemberSwitchedTeam),
+            /// Sent to a client when it was removed from its lobby, e.g. because it left or was kicked
+            /// Can not be sent by a client, only by the server
+            RemovedFromLobby(RemovedFromLobby),
+            /// Sent to the lobby by a player to tell whether it is ready for the game to start
+            #[target(ToLobbyDirectly)]
+            SetReady(SetReady),
+            /// Sent to all members of a lobby whenever its members change, e.g. when a player joins, switches its team or is ready
+            /// Can not be sent by a client, only by the server
+            LobbyRoster(LobbyRoster),
+            /// Sent to the lobby by its host to remove a member
+            #[target(ToLobbyDirectly)]
+            KickMember(KickMember),
+            /// Sent to the lobby by its host to make another member the host
+            #[target(ToLobbyDirectly)]
+            TransferHost(TransferHost),
+            /// Sent to the lobby by its host to change the password, join codes or auto start, only before the game started
+            #[target(ToLobbyDirectly)]
+            ChangeLobbySettings(ChangeLobbySettings),
+            /// Sent to the lobby by its host to play again once the game finished
+            #[target(ToLobbyDirectly)]
+            Rematch(Rematch),
             /// Sent from the client to the server to move the tank
             /// Will only be sent by a client
             /// Can only be sent to itself on the server
@@ -97,6 +166,12 @@ use super::message_data::{
             RotateTankTurretCommand(RotateTankTurretCommand),
             #[target(ToSelf)]
             ShootCommand(ShootCommand),
+            /// Sent from the client to the server to start or stop repairing the tank
+            #[target(ToSelf)]
+            RepairCommand(RepairCommand),
+            /// Sent from the client to the server to drop a mine behind the tank
+            #[target(ToSelf)]
+            DeployMineCommand(DeployMineCommand),
         }
     }
 )]
@@ -0,0 +1,84 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::networking::messages::message_container::NetworkMessageType;
+
+use super::tank_messages::{
+    deploy_mine::DeployMineCommand, move_tank::MoveTankCommand, repair::RepairCommand,
+    rotate_tank_body::RotateTankBodyCommand, rotate_tank_turret::RotateTankTurretCommand,
+    shoot::ShootCommand,
+};
+
+/// Sent to the client in the next game state for every command it sent
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct CommandAcknowledgement {
+    /// The command with the values that were actually applied (e.g. after clamping them to the limits of the tank)
+    /// Rejected commands are sent back unchanged
+    pub command: AcknowledgedCommand,
+    /// The tick the client set when sending the command, to match the acknowledgement to it
+    pub tick_sent: u64,
+    /// The tick the command was processed on
+    pub tick_processed: u64,
+    /// Why the command was not applied, None if it was applied
+    pub rejection_reason: Option<CommandRejectionReason>,
+}
+
+/// The commands a client can send to control its tank
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(tag = "message_type")]
+pub enum AcknowledgedCommand {
+    MoveTankCommand(MoveTankCommand),
+    RotateTankBodyCommand(RotateTankBodyCommand),
+    RotateTankTurretCommand(RotateTankTurretCommand),
+    ShootCommand(ShootCommand),
+    RepairCommand(RepairCommand),
+    DeployMineCommand(DeployMineCommand),
+}
+
+impl AcknowledgedCommand {
+    /// Returns the command contained in the message, None if the message is not a command
+    pub fn from_message(message: &NetworkMessageType) -> Option<Self> {
+        match message {
+            NetworkMessageType::MoveTankCommand(command) => {
+                Some(AcknowledgedCommand::MoveTankCommand(command.clone()))
+            }
+            NetworkMessageType::RotateTankBodyCommand(command) => {
+                Some(AcknowledgedCommand::RotateTankBodyCommand(command.clone()))
+            }
+            NetworkMessageType::RotateTankTurretCommand(command) => Some(
+                AcknowledgedCommand::RotateTankTurretCommand(command.clone()),
+            ),
+            NetworkMessageType::ShootCommand(command) => {
+                Some(AcknowledgedCommand::ShootCommand(command.clone()))
+            }
+            NetworkMessageType::RepairCommand(command) => {
+                Some(AcknowledgedCommand::RepairCommand(command.clone()))
+            }
+            NetworkMessageType::DeployMineCommand(command) => {
+                Some(AcknowledgedCommand::DeployMineCommand(command.clone()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Why a command was not applied
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum CommandRejectionReason {
+    /// The command was scheduled for a later tick than the one being processed
+    ScheduledForLaterTick,
+    /// The tank is dead
+    Dead,
+    /// The gun is still reloading
+    OnCooldown,
+    /// The command was sent to a target it is not allowed for
+    InvalidTarget,
+    /// The tracks are broken or the tank is repairing
+    Immobilized,
+    /// The turret ring is broken
+    TurretJammed,
+    /// All mines have already been deployed
+    NoMinesLeft,
+}
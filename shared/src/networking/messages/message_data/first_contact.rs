@@ -1,8 +1,10 @@
-use bevy::prelude::*;
+use bevy::{prelude::*, utils::HashMap};
 use serde::{Deserialize, Serialize};
 
 use crate::game::tank_types::TankType;
 
+use super::{protocol_negotiation::ProtocolCapability, start_game_config::StartGameConfig};
+
 #[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct FirstContactData {
@@ -11,9 +13,42 @@ pub struct FirstContactData {
     pub map_name: Option<String>,
     pub client_type: ClientType,
 
+    /// The team to join, players without a team are put into the team with the most free slots
     pub team_name: Option<String>,
     pub bot_assigned_spawn_point: Option<usize>,
     pub tank_type: Option<TankType>,
+    /// The skill of the bot, if set it breaks ties between the teams with the most free slots for players without a team
+    /// The team with the lowest average rating (counting only rated players) is chosen
+    #[serde(default)]
+    pub skill_rating: Option<f32>,
+
+    /// The password of the lobby
+    /// Sets the password if the lobby gets created by this client, has to match it otherwise
+    #[serde(default)]
+    pub lobby_password: Option<String>,
+    /// The join code of the team to join, only needed if the lobby creator set one for the team
+    #[serde(default)]
+    pub team_join_code: Option<String>,
+    /// The join codes of the teams (team name -> join code), only used if the lobby gets created by this client
+    #[serde(default)]
+    pub team_join_codes: HashMap<String, String>,
+    /// Starts the lobby with this config as soon as all players are ready, only used if the lobby gets created by this client
+    #[serde(default)]
+    pub auto_start: Option<StartGameConfig>,
+
+    /// The protocol version the client speaks, has to match the server's version
+    #[serde(default)]
+    pub protocol_version: Option<u32>,
+    /// The optional capabilities the client would like to use
+    /// The server answers with the ones it supports as well
+    #[serde(default)]
+    pub capabilities: Vec<ProtocolCapability>,
+    /// The admin password of the server, makes the client host of the lobby it joins
+    #[serde(default)]
+    pub admin_password: Option<String>,
+    /// The session token received when joining a lobby, to reconnect to a running match after losing the connection
+    #[serde(default)]
+    pub session_token: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq, Component, Default)]
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Sent to all members of a lobby once at most one team has living tanks left
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct GameFinished {
+    /// None if the last tanks of all teams died in the same tick
+    pub winning_team: Option<String>,
+}
@@ -1,7 +1,9 @@
 use bevy::{prelude::*, utils::HashMap};
 use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
-use crate::game::game_state::{ClientState, ProjectileState};
+use crate::game::game_state::{ClientState, MineState, ProjectileState};
+
+use super::command_acknowledgement::CommandAcknowledgement;
 
 #[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq, Default)]
 #[serde(rename_all = "camelCase")]
@@ -17,8 +19,125 @@ pub struct GameState {
         deserialize_with = "deserialize_hashmap"
     )]
     pub projectile_states: HashMap<Entity, ProjectileState>,
+    #[serde(
+        serialize_with = "serialize_hashmap",
+        deserialize_with = "deserialize_hashmap"
+    )]
+    pub mine_states: HashMap<Entity, MineState>,
+    /// The commands of the client that were processed since the last state
+    pub command_acknowledgements: Vec<CommandAcknowledgement>,
+}
+
+/// The changes to the game state since an earlier state the client acknowledged (see `AcknowledgeGameState`)
+/// Only sent to clients that negotiated `ProtocolCapability::DeltaStates`, instead of the full `GameState`
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct GameStateDelta {
+    pub tick: u64,
+    /// The tick of the acknowledged state these changes have to be applied to
+    pub base_tick: u64,
+    /// The client states that changed or were added since the base state
+    #[serde(
+        serialize_with = "serialize_hashmap",
+        deserialize_with = "deserialize_hashmap"
+    )]
+    pub client_states: HashMap<Entity, Option<ClientState>>,
+    /// The projectile states that changed or were added since the base state
+    #[serde(
+        serialize_with = "serialize_hashmap",
+        deserialize_with = "deserialize_hashmap"
+    )]
+    pub projectile_states: HashMap<Entity, ProjectileState>,
+    /// The mine states that changed or were added since the base state
+    #[serde(
+        serialize_with = "serialize_hashmap",
+        deserialize_with = "deserialize_hashmap"
+    )]
+    pub mine_states: HashMap<Entity, MineState>,
+    /// The clients, projectiles and mines that are no longer part of the game state
+    pub removed_entities: Vec<Entity>,
+    /// The commands of the client that were processed since the last state, they are not part of the delta
+    pub command_acknowledgements: Vec<CommandAcknowledgement>,
+}
+
+impl GameStateDelta {
+    /// Collects everything that changed from the base to the current state
+    pub fn between(base: &GameState, current: &GameState) -> Self {
+        let mut removed_entities = Vec::new();
+
+        Self {
+            tick: current.tick,
+            base_tick: base.tick,
+            client_states: changed_entries(
+                &base.client_states,
+                &current.client_states,
+                &mut removed_entities,
+            ),
+            projectile_states: changed_entries(
+                &base.projectile_states,
+                &current.projectile_states,
+                &mut removed_entities,
+            ),
+            mine_states: changed_entries(
+                &base.mine_states,
+                &current.mine_states,
+                &mut removed_entities,
+            ),
+            removed_entities,
+            command_acknowledgements: current.command_acknowledgements.clone(),
+        }
+    }
+
+    /// Applies the changes to the base state, turning it into the state of this delta's tick
+    pub fn apply_to(&self, base: &mut GameState) {
+        base.tick = self.tick;
+
+        for entity in self.removed_entities.iter() {
+            base.client_states.remove(entity);
+            base.projectile_states.remove(entity);
+            base.mine_states.remove(entity);
+        }
+
+        base.client_states.extend(self.client_states.clone());
+        base.projectile_states
+            .extend(self.projectile_states.clone());
+        base.mine_states.extend(self.mine_states.clone());
+        base.command_acknowledgements = self.command_acknowledgements.clone();
+    }
+}
+
+/// Returns the entries that are new or differ from the base, and adds the entities missing in the current map to `removed`
+fn changed_entries<V: Clone + PartialEq>(
+    base: &HashMap<Entity, V>,
+    current: &HashMap<Entity, V>,
+    removed: &mut Vec<Entity>,
+) -> HashMap<Entity, V> {
+    removed.extend(
+        base.keys()
+            .filter(|entity| !current.contains_key(*entity))
+            .copied(),
+    );
+
+    current
+        .iter()
+        .filter(|(entity, value)| base.get(*entity) != Some(*value))
+        .map(|(entity, value)| (*entity, value.clone()))
+        .collect()
+}
+
+/// Sent by clients using `ProtocolCapability::DeltaStates` for every game state (or delta) they received
+/// Following deltas are based on the newest acknowledged state
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct AcknowledgeGameState {
+    pub tick: u64,
 }
 
+/// Asks the server to send the next game state in full, e.g. if the client lost track of its states
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestFullGameState;
+
 fn serialize_hashmap<S, V>(map: &HashMap<Entity, V>, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -49,3 +168,95 @@ where
         })
         .collect()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn game_state(tick: u64) -> GameState {
+        GameState { tick, ..default() }
+    }
+
+    #[test]
+    fn delta_applied_to_base_results_in_current_state() {
+        let kept_client = Entity::from_raw(1);
+        let changed_client = Entity::from_raw(2);
+        let removed_client = Entity::from_raw(3);
+        let added_projectile = Entity::from_raw(4);
+        let removed_projectile = Entity::from_raw(5);
+        let changed_mine = Entity::from_raw(6);
+
+        let mut base = game_state(10);
+        base.client_states
+            .insert(kept_client, Some(ClientState::new(kept_client)));
+        base.client_states
+            .insert(changed_client, Some(ClientState::new(changed_client)));
+        base.client_states.insert(removed_client, None);
+        base.projectile_states.insert(
+            removed_projectile,
+            ProjectileState::new(removed_projectile, kept_client, Transform::default()),
+        );
+        base.mine_states.insert(
+            changed_mine,
+            MineState::new(changed_mine, changed_client, Transform::default()),
+        );
+
+        let mut current = base.clone();
+        current.tick = 12;
+        current.client_states.remove(&removed_client);
+        current
+            .client_states
+            .get_mut(&changed_client)
+            .unwrap()
+            .as_mut()
+            .unwrap()
+            .health = Some(50.0);
+        current.projectile_states.remove(&removed_projectile);
+        current.projectile_states.insert(
+            added_projectile,
+            ProjectileState::new(
+                added_projectile,
+                changed_client,
+                Transform::from_xyz(1.0, 0.0, 2.0),
+            ),
+        );
+        current
+            .mine_states
+            .get_mut(&changed_mine)
+            .unwrap()
+            .transform = Transform::from_xyz(3.0, 0.0, 4.0);
+
+        let delta = GameStateDelta::between(&base, &current);
+        assert_eq!(delta.tick, 12);
+        assert_eq!(delta.base_tick, 10);
+        assert!(!delta.client_states.contains_key(&kept_client));
+        assert!(delta.client_states.contains_key(&changed_client));
+        assert!(delta.projectile_states.contains_key(&added_projectile));
+        assert!(delta.mine_states.contains_key(&changed_mine));
+        assert_eq!(delta.removed_entities.len(), 2);
+        assert!(delta.removed_entities.contains(&removed_client));
+        assert!(delta.removed_entities.contains(&removed_projectile));
+
+        delta.apply_to(&mut base);
+        assert_eq!(base, current);
+    }
+
+    #[test]
+    fn delta_between_equal_states_is_empty() {
+        let client = Entity::from_raw(1);
+        let mut base = game_state(3);
+        base.client_states
+            .insert(client, Some(ClientState::new(client)));
+        let mut current = base.clone();
+        current.tick = 4;
+
+        let delta = GameStateDelta::between(&base, &current);
+        assert!(delta.client_states.is_empty());
+        assert!(delta.projectile_states.is_empty());
+        assert!(delta.mine_states.is_empty());
+        assert!(delta.removed_entities.is_empty());
+
+        delta.apply_to(&mut base);
+        assert_eq!(base, current);
+    }
+}
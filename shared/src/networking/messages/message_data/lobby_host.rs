@@ -0,0 +1,46 @@
+use bevy::{prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use super::start_game_config::StartGameConfig;
+
+/// Sent by the host to remove a member from the lobby
+/// Before the game started the member can join again, during a match it gets disconnected
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct KickMember {
+    pub client_id: Entity,
+}
+
+/// Sent by the host to make another member the host
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferHost {
+    pub client_id: Entity,
+}
+
+/// Sent by the host to replace the settings of the lobby before the game starts
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChangeLobbySettings {
+    /// None removes the password
+    #[serde(default)]
+    pub password: Option<String>,
+    /// The join codes of the teams (team name -> join code)
+    #[serde(default)]
+    pub team_join_codes: HashMap<String, String>,
+    /// None disables starting automatically
+    #[serde(default)]
+    pub auto_start: Option<StartGameConfig>,
+}
+
+/// Sent by the host to play again after the game finished, with the same members in the same teams
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Rematch {
+    /// Every team moves to the spawn points of the next team
+    #[serde(default)]
+    pub swap_sides: bool,
+    /// Plays the next map (sorted by name) that has room for all teams
+    #[serde(default)]
+    pub next_map: bool,
+}
@@ -0,0 +1,40 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::networking::lobby_management::LobbyState;
+
+/// Asks the server for all lobbies and maps, can be sent before the first contact
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestLobbyList;
+
+/// The answer to a `RequestLobbyList`
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyList {
+    pub lobbies: Vec<LobbyInfo>,
+    /// The names of all maps a new lobby can be created with
+    pub maps: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyInfo {
+    pub lobby_name: String,
+    pub state: LobbyState,
+    pub map_name: String,
+    pub teams: Vec<TeamInfo>,
+    pub spectators: usize,
+    /// Whether the lobby can only be joined with a password
+    pub has_password: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct TeamInfo {
+    pub team_name: String,
+    pub players: usize,
+    pub max_players: usize,
+    /// Whether the team can only be joined with a join code
+    pub has_join_code: bool,
+}
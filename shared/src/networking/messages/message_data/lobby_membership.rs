@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Sent by a client to leave its lobby before the game starts
+/// Afterwards the client can join a lobby again by sending a new first contact
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LeaveLobby;
+
+/// Sent by a player to switch to another team before the game starts
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SwitchTeam {
+    pub team_name: String,
+    /// Only needed if the lobby creator set a join code for the team
+    #[serde(default)]
+    pub team_join_code: Option<String>,
+}
+
+/// Sent to all members of a lobby when a client left it
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberLeftLobby {
+    pub client_id: Entity,
+    pub client_name: String,
+}
+
+/// Sent to all members of a lobby when a player switched its team
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MemberSwitchedTeam {
+    pub client_id: Entity,
+    pub client_name: String,
+    pub team_name: String,
+}
+
+/// Sent to a client when it is no longer a member of its lobby
+/// Afterwards the client can join a lobby again by sending a new first contact
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RemovedFromLobby {
+    pub lobby_name: String,
+    pub reason: LobbyRemovalReason,
+}
+
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum LobbyRemovalReason {
+    /// The client sent `LeaveLobby`
+    Left,
+    /// The host kicked the client
+    Kicked,
+    /// The lobby got removed, e.g. because its map couldn't be loaded
+    LobbyClosed,
+    /// The client lost its connection
+    Disconnected,
+}
@@ -0,0 +1,38 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{game::tank_types::TankType, networking::lobby_management::LobbyState};
+
+use super::first_contact::ClientType;
+
+/// Sent by a player to tell the lobby whether it is ready for the game to start
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SetReady {
+    pub ready: bool,
+}
+
+/// Sent to all members of a lobby whenever its members change
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyRoster {
+    pub lobby_name: String,
+    pub state: LobbyState,
+    pub map_name: String,
+    /// The member allowed to kick members, force a start and change the settings
+    pub host: Option<Entity>,
+    pub members: Vec<LobbyMember>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct LobbyMember {
+    pub client_id: Entity,
+    pub client_name: String,
+    pub client_type: ClientType,
+    /// None for spectators
+    pub team_name: Option<String>,
+    /// None for spectators
+    pub tank_type: Option<TankType>,
+    pub ready: bool,
+}
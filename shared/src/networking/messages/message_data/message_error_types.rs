@@ -15,4 +15,11 @@ pub enum ErrorMessageTypes {
     TeamDoesNotExist(String),
     TeamFull(String),
     InvalidFirstContact(String),
+    UnsupportedProtocolVersion(String),
+    FrameTooLarge(String),
+    RateLimitExceeded(String),
+    InvalidSessionToken(String),
+    WrongLobbyPassword(String),
+    WrongTeamJoinCode(String),
+    NotLobbyHost(String),
 }
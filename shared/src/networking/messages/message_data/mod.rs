@@ -1,9 +1,17 @@
 use bevy::prelude::*;
 
+pub mod command_acknowledgement;
 pub mod first_contact;
+pub mod game_finished;
 pub mod game_starts;
 pub mod game_state;
+pub mod lobby_host;
+pub mod lobby_list;
+pub mod lobby_membership;
+pub mod lobby_roster;
 pub mod message_error_types;
+pub mod protocol_negotiation;
+pub mod session_token;
 pub mod start_game_config;
 pub mod tank_messages;
 pub mod text_data;
@@ -15,10 +23,37 @@ impl Plugin for MySharedMessageDataPlugin {
         app.register_type::<first_contact::FirstContactData>()
             .register_type::<first_contact::ClientType>()
             .register_type::<message_error_types::ErrorMessageTypes>()
+            .register_type::<game_finished::GameFinished>()
             .register_type::<game_starts::GameStarts>()
             .register_type::<game_starts::ConnectedClientConfig>()
             .register_type::<text_data::TextDataWrapper>()
             .register_type::<game_state::GameState>()
+            .register_type::<game_state::GameStateDelta>()
+            .register_type::<game_state::AcknowledgeGameState>()
+            .register_type::<game_state::RequestFullGameState>()
+            .register_type::<command_acknowledgement::CommandAcknowledgement>()
+            .register_type::<command_acknowledgement::AcknowledgedCommand>()
+            .register_type::<command_acknowledgement::CommandRejectionReason>()
+            .register_type::<lobby_host::KickMember>()
+            .register_type::<lobby_host::TransferHost>()
+            .register_type::<lobby_host::ChangeLobbySettings>()
+            .register_type::<lobby_host::Rematch>()
+            .register_type::<lobby_list::RequestLobbyList>()
+            .register_type::<lobby_list::LobbyList>()
+            .register_type::<lobby_list::LobbyInfo>()
+            .register_type::<lobby_list::TeamInfo>()
+            .register_type::<lobby_membership::LeaveLobby>()
+            .register_type::<lobby_membership::SwitchTeam>()
+            .register_type::<lobby_membership::MemberLeftLobby>()
+            .register_type::<lobby_membership::MemberSwitchedTeam>()
+            .register_type::<lobby_membership::RemovedFromLobby>()
+            .register_type::<lobby_membership::LobbyRemovalReason>()
+            .register_type::<lobby_roster::SetReady>()
+            .register_type::<lobby_roster::LobbyRoster>()
+            .register_type::<lobby_roster::LobbyMember>()
+            .register_type::<protocol_negotiation::ProtocolCapability>()
+            .register_type::<protocol_negotiation::NegotiatedProtocol>()
+            .register_type::<session_token::SessionToken>()
             .add_plugins((tank_messages::MyTankMessagesPlugin,));
     }
 }
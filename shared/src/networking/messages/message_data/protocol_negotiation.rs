@@ -0,0 +1,30 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Optional protocol features a client can ask for in its first contact
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ProtocolCapability {
+    /// Messages are encoded in a binary format instead of JSON
+    BinaryEncoding,
+    /// Game states only contain what changed since the last acknowledged state
+    DeltaStates,
+    /// The client receives game events (e.g. hits, deaths) in addition to the game states
+    Events,
+}
+
+/// The protocol version and the capabilities both the client and the server support
+/// Sent to the client as the answer to its first contact, and stored on the client
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq, Component, Default)]
+#[reflect(Component)]
+#[serde(rename_all = "camelCase")]
+pub struct NegotiatedProtocol {
+    pub protocol_version: u32,
+    pub capabilities: Vec<ProtocolCapability>,
+}
+
+impl NegotiatedProtocol {
+    pub fn has_capability(&self, capability: &ProtocolCapability) -> bool {
+        self.capabilities.contains(capability)
+    }
+}
@@ -0,0 +1,10 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Sent to a player when joining a lobby
+/// The player can present it in the first contact to reconnect to the match after losing its connection
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct SessionToken {
+    pub token: String,
+}
@@ -5,4 +5,7 @@ use serde::{Deserialize, Serialize};
 #[serde(rename_all = "camelCase")]
 pub struct StartGameConfig {
     pub fill_empty_slots_with_dummies: bool,
+    /// Starts even if not all players are ready, only the host of the lobby can force a start
+    #[serde(default)]
+    pub force: bool,
 }
@@ -0,0 +1,8 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A command to drop a mine behind the tank
+/// Each tank can only deploy a limited amount of mines per life
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployMineCommand;
@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 
+pub mod deploy_mine;
 pub mod move_tank;
+pub mod repair;
 pub mod rotate_tank_body;
 pub mod rotate_tank_turret;
 pub mod shoot;
@@ -12,6 +14,8 @@ impl Plugin for MyTankMessagesPlugin {
         app.register_type::<move_tank::MoveTankCommand>()
             .register_type::<rotate_tank_body::RotateTankBodyCommand>()
             .register_type::<rotate_tank_turret::RotateTankTurretCommand>()
-            .register_type::<shoot::ShootCommand>();
+            .register_type::<shoot::ShootCommand>()
+            .register_type::<repair::RepairCommand>()
+            .register_type::<deploy_mine::DeployMineCommand>();
     }
 }
@@ -5,6 +5,8 @@ use serde::{Deserialize, Serialize};
 /// You can move the tank in the given direction by the given distance
 /// The distance is not allowed to be higher than the tank's maximum speed, but it can be lower
 /// The speed/distance is the distance traveled in one tick
+/// If the tank has momentum (an acceleration is configured), the distance is the speed the tank accelerates or brakes towards instead.
+/// It stays in effect until the next command, so send a distance of 0 to stop the tank
 #[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct MoveTankCommand {
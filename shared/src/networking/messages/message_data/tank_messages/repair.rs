@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// A command to start or stop repairing the tank
+/// While repairing, the tank heals faster but can't move or rotate its body
+/// It stays in effect until the next repair command or until the tank dies
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct RepairCommand {
+    pub active: bool,
+}
@@ -4,7 +4,9 @@ use messages::MySharedNetworkMessagesPlugin;
 use networking_state::MyNetworkingState;
 use networking_system_sets::MyNetworkingSet;
 
+pub mod framing;
 pub mod lobby_management;
+pub mod message_codec;
 pub mod messages;
 pub mod networking_state;
 pub mod networking_system_sets;
@@ -14,6 +16,7 @@ pub struct MySharedNetworkingPlugin;
 impl Plugin for MySharedNetworkingPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((MySharedNetworkMessagesPlugin, MyLobbyManagementPlugin))
+            .register_type::<message_codec::MessageEncoding>()
             .add_sub_state::<MyNetworkingState>()
             .configure_sets(
                 Update,
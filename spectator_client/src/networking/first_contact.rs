@@ -1,10 +1,13 @@
 use bevy::prelude::*;
 use shared::{
     asset_handling::config::ClientConfigSystemParam,
-    networking::messages::{
-        message_container::{MessageContainer, MessageTarget, NetworkMessageType},
-        message_data::first_contact::{ClientType, FirstContactData},
-        message_queue::ImmediateOutMessageQueue,
+    networking::{
+        framing::PROTOCOL_VERSION,
+        messages::{
+            message_container::{MessageContainer, MessageTarget, NetworkMessageType},
+            message_data::first_contact::{ClientType, FirstContactData},
+            message_queue::ImmediateOutMessageQueue,
+        },
     },
 };
 
@@ -26,6 +29,9 @@ pub fn send_first_contact(
                 bot_name: client_config.name.clone(),
                 map_name: Some(client_config.map.clone()),
                 lobby_name: client_config.lobby_name.clone(),
+                lobby_password: client_config.lobby_password.clone(),
+                admin_password: client_config.admin_password.clone(),
+                protocol_version: Some(PROTOCOL_VERSION),
                 ..default()
             }),
         ));
@@ -3,7 +3,8 @@ use std::net::TcpStream;
 use bevy::prelude::*;
 use message_handling::MyMessageHandlingPlugin;
 use shared::{
-    main_state::MyMainState, networking::messages::message_queue::ImmediateOutMessageQueue,
+    main_state::MyMainState,
+    networking::{framing::FrameCodec, messages::message_queue::ImmediateOutMessageQueue},
 };
 
 pub mod connect;
@@ -21,5 +22,5 @@ impl Plugin for MyNetworkingPlugin {
 }
 
 #[derive(Component, Debug, Deref, DerefMut)]
-#[require(ImmediateOutMessageQueue)]
+#[require(ImmediateOutMessageQueue, FrameCodec)]
 pub struct MyNetworkStream(pub TcpStream);
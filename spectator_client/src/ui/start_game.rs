@@ -18,7 +18,10 @@ use super::{interaction_system::ButtonPressedTrigger, NORMAL_BUTTON_COLOR};
 
 #[derive(Debug, Component, Default, Reflect)]
 #[reflect(Component)]
-pub struct StartGameButton;
+pub struct StartGameButton {
+    /// Starts without waiting for the players to be ready, only the host can do this
+    pub force: bool,
+}
 
 pub fn spawn_start_game_button(mut commands: Commands) {
     commands
@@ -27,47 +30,58 @@ pub fn spawn_start_game_button(mut commands: Commands) {
             Node {
                 width: Val::Percent(100.0),
                 height: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(20.0),
                 align_items: AlignItems::Center,
                 justify_content: JustifyContent::Center,
                 ..default()
             },
         ))
         .with_children(|commands| {
-            commands
-                .spawn((
-                    Button,
-                    StartGameButton,
-                    Node {
-                        width: Val::Px(250.0),
-                        height: Val::Px(75.0),
-                        border: UiRect::all(Val::Px(5.0)),
-                        justify_content: JustifyContent::Center,
-                        align_items: AlignItems::Center,
-                        ..default()
-                    },
-                    BorderColor(Color::BLACK),
-                    BorderRadius::MAX,
-                    BackgroundColor(NORMAL_BUTTON_COLOR),
-                ))
-                .observe(button_pressed)
-                .with_child((
-                    Text::new("Start Game"),
-                    TextFont {
-                        font_size: 30.0,
-                        ..default()
-                    },
-                    TextColor(Color::WHITE),
-                ));
+            spawn_button(commands, "Start Game", false);
+            spawn_button(commands, "Force Start", true);
         });
 }
 
+fn spawn_button(commands: &mut ChildBuilder, text: &str, force: bool) {
+    commands
+        .spawn((
+            Button,
+            StartGameButton { force },
+            Node {
+                width: Val::Px(250.0),
+                height: Val::Px(75.0),
+                border: UiRect::all(Val::Px(5.0)),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BorderColor(Color::BLACK),
+            BorderRadius::MAX,
+            BackgroundColor(NORMAL_BUTTON_COLOR),
+        ))
+        .observe(button_pressed)
+        .with_child((
+            Text::new(text),
+            TextFont {
+                font_size: 30.0,
+                ..default()
+            },
+            TextColor(Color::WHITE),
+        ));
+}
+
 fn button_pressed(
-    _: Trigger<ButtonPressedTrigger>,
+    trigger: Trigger<ButtonPressedTrigger>,
+    buttons: Query<&StartGameButton>,
     mut message_queue: Query<&mut ImmediateOutMessageQueue>,
     mut state: ResMut<NextState<MyGameState>>,
     client_config: ClientConfigSystemParam,
 ) {
     let client_config = client_config.client_config();
+    let button = buttons
+        .get(trigger.entity())
+        .expect("Failed to get start game button");
 
     state.set(MyGameState::GameToldToStart);
 
@@ -76,6 +90,8 @@ fn button_pressed(
             MessageTarget::ToLobbyDirectly,
             NetworkMessageType::StartGame(StartGameConfig {
                 fill_empty_slots_with_dummies: client_config.fill_empty_slots_with_dummies,
+                // Only the host can skip waiting for the players to be ready
+                force: button.force,
             }),
         ));
     }
@@ -86,7 +102,8 @@ pub fn start_game_error_handling(
     mut state: ResMut<NextState<MyGameState>>,
 ) {
     match &(**trigger.event()) {
-        ErrorMessageTypes::LobbyNotReadyToStart(message) => {
+        ErrorMessageTypes::LobbyNotReadyToStart(message)
+        | ErrorMessageTypes::NotLobbyHost(message) => {
             error!("Failed to start game: {}", message);
             state.set(MyGameState::SettingUp);
         }